@@ -3,6 +3,7 @@
 use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
@@ -132,10 +133,140 @@ pub struct InitFlags {
   pub dir: Option<String>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct InfoFlags {
   pub json: bool,
-  pub file: Option<String>,
+  pub file: Vec<String>,
+  pub dot: bool,
+  pub mermaid: bool,
+  pub depth: Option<u32>,
+  pub npm_only: bool,
+  pub sort: InfoSortOrder,
+  pub show_licenses: bool,
+  pub show_types: bool,
+  pub compact: bool,
+  pub importers: Option<String>,
+  pub path_to: Option<String>,
+  pub by_host: bool,
+  /// Mirrors the global `--no-npm` flag to exclude npm packages.
+  pub no_npm: bool,
+  pub out: Option<PathBuf>,
+  pub compressed: bool,
+  pub verbose_dedup: bool,
+  pub filter: Option<String>,
+  pub stats: bool,
+  pub show_cache: bool,
+  pub exclude: Vec<String>,
+  pub exclude_from_totals: bool,
+  pub color: InfoColorMode,
+  pub subtree_size: bool,
+  pub flat: bool,
+  pub show_checksums: bool,
+  pub sizes: bool,
+  pub cache_usage: bool,
+  pub roots_only: bool,
+  pub sbom: bool,
+  pub color_by_type: bool,
+  pub duplicates: bool,
+  pub summary: bool,
+  pub warn_size: Option<u64>,
+  pub fail_on_warn: bool,
+  pub show_emit: bool,
+  pub show_config: bool,
+  pub lock_check: bool,
+  pub show_npm_urls: bool,
+  pub diff: Option<String>,
+  pub show_dep_counts: bool,
+  pub ascii: bool,
+  pub no_type_deps: bool,
+  pub watch: Option<WatchFlags>,
+  pub why: Option<String>,
+  pub csv: bool,
+  pub abbreviate: bool,
+  pub max_nodes: Option<u32>,
+  pub jsonl: bool,
+  pub show_locations: bool,
+  pub percent: bool,
+  pub top: Option<u32>,
+  pub vendor_check: Option<PathBuf>,
+  pub kind: InfoGraphKind,
+  pub relative: bool,
+  pub focus: Option<String>,
+  pub npm_usage: bool,
+  pub count_only: bool,
+  pub size_only: bool,
+  pub verbose: bool,
+  pub format: Option<Vec<InfoFormatToken>>,
+  pub group_npm_by_root: bool,
+  pub fail_on_insecure: bool,
+  pub resolve_only: Option<String>,
+  pub treemap_json: bool,
+  pub split_sizes: bool,
+  pub no_dedup: bool,
+  pub show_import_map: bool,
+  pub entrypoint_from_config: bool,
+  pub width: Option<u32>,
+  pub runtime_only: bool,
+  pub legend: bool,
+  pub cache_only: bool,
+}
+
+/// A part of a `--format` template for the flat list of modules.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InfoFormatToken {
+  /// Text that's written as is.
+  Text(String),
+  /// `{specifier}`
+  Specifier,
+  /// `{size}`, in bytes.
+  Size,
+  /// `{media_type}`
+  MediaType,
+  /// `{dependency_count}`
+  DependencyCount,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InfoSortOrder {
+  /// Sort dependencies alphabetically.
+  #[default]
+  Name,
+  /// Sort dependencies by size, largest first.
+  Size,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InfoGraphKind {
+  /// Include both the code and the type dependencies.
+  #[default]
+  All,
+  /// Only include the code dependencies, which skips fetching the type
+  /// declarations.
+  CodeOnly,
+  /// Only include the type dependencies.
+  TypesOnly,
+}
+
+impl InfoGraphKind {
+  /// Gets the module `GraphKind` that should be created for this kind.
+  pub fn as_graph_kind(&self) -> GraphKind {
+    match self {
+      Self::All => GraphKind::All,
+      Self::CodeOnly => GraphKind::CodeOnly,
+      Self::TypesOnly => GraphKind::TypesOnly,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InfoColorMode {
+  /// Use colors unless the `NO_COLOR` environment variable is set.
+  #[default]
+  Auto,
+  /// Always use colors.
+  Always,
+  /// Never use colors.
+  Never,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1394,14 +1525,51 @@ type: JavaScript, TypeScript, or JSON.
 emit: Local path of compiled source code. (TypeScript only.)
 dependencies: Dependency tree of the source file.
 
+Pass '-' to read the module specifier from stdin:
+
+  echo ./mod.ts | deno info -
+
+Pass --entrypoint-from-config to show the module run by the 'start' task of
+the deno.json, or the 'main' of the package.json:
+
+  deno info --entrypoint-from-config
+
+Pass multiple modules to show a separate graph for each of them. With
+'--json', the graphs are written as an array and each graph has the
+specifier of its module:
+
+  deno info a.ts b.ts
+
+The JSON output for a module contains a 'version' field, which is
+incremented whenever the shape of the output changes. With multiple modules,
+each graph of the array contains it.
+
 Without any additional arguments, 'deno info' shows:
 
 DENO_DIR: Directory containing Deno-managed files.
 Remote modules cache: Subdirectory containing downloaded remote modules.
 TypeScript compiler cache: Subdirectory containing TS compiler output.",
       )
-      .arg(Arg::new("file").required(false).value_hint(ValueHint::FilePath))
-      .arg(reload_arg().requires("file"))
+      .arg(
+        Arg::new("file")
+          .num_args(1..)
+          .required(false)
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("entrypoint-from-config")
+          .long("entrypoint-from-config")
+          .conflicts_with("file")
+          .help("Show the module run by the 'start' task of the deno.json or the 'main' of the package.json. Shows the cache locations when neither is found")
+          .action(ArgAction::SetTrue),
+      )
+      // the options for showing a module also apply to the module of the config
+      .group(
+        ArgGroup::new("module")
+          .args(["file", "entrypoint-from-config"])
+          .multiple(false),
+      )
+      .arg(reload_arg().requires("module"))
       .arg(ca_file_arg())
       .arg(
         location_arg()
@@ -1422,6 +1590,559 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
           .long("json")
           .help("UNSTABLE: Outputs the information in JSON format")
           .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("dot")
+          .long("dot")
+          .requires("module")
+          .conflicts_with("json")
+          .help("Outputs the module graph in Graphviz DOT format")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("mermaid")
+          .long("mermaid")
+          .requires("module")
+          .conflicts_with("json")
+          .help("Outputs the module graph as a Mermaid flowchart")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("depth")
+          .long("depth")
+          .requires("module")
+          .value_name("N")
+          .value_parser(value_parser!(u32))
+          .help("Limit the depth of the displayed dependency tree"),
+      )
+      .arg(
+        Arg::new("npm-only")
+          .long("npm-only")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "no-npm"])
+          .help("Only show the npm packages in the dependency tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("sort")
+          .long("sort")
+          .requires("module")
+          .value_parser(["name", "size"])
+          .help("Order in which dependencies are displayed in the tree"),
+      )
+      .arg(
+        Arg::new("show-licenses")
+          .long("show-licenses")
+          .requires("module")
+          .help("Show the license of each npm package in the tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-types")
+          .long("show-types")
+          .requires("module")
+          .help("Show the media type of each module in the tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("compact")
+          .long("compact")
+          .requires("json")
+          .help("Outputs the JSON on a single line instead of pretty printing it")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("importers")
+          .long("importers")
+          .requires("module")
+          .value_name("SPECIFIER")
+          .help("Lists the modules in the graph that import the specified module")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("path-to")
+          .long("path-to")
+          .requires("module")
+          .conflicts_with("json")
+          .value_name("SPECIFIER")
+          .help("Shows the shortest import chain from the root to the specified module")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("by-host")
+          .long("by-host")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "importers", "path-to"])
+          .help("Shows the module count and size per host instead of the dependency tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("out")
+          .long("out")
+          .requires("module")
+          .value_name("FILE")
+          .help("Write the output to the specified file instead of stdout")
+          .value_parser(value_parser!(PathBuf))
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("compressed")
+          .long("compressed")
+          .requires("module")
+          .help("Show an estimate of the gzip compressed size of the modules")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("verbose-dedup")
+          .long("verbose-dedup")
+          .requires("module")
+          .help("Show how many dependencies were collapsed for npm packages that were already displayed and which module first imported the modules that were already displayed")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("filter")
+          .long("filter")
+          .requires("module")
+          .conflicts_with_all(["json", "dot", "mermaid", "npm-only"])
+          .value_name("GLOB")
+          .help("Only show the modules matching the glob and the modules that import them"),
+      )
+      .arg(
+        Arg::new("stats")
+          .long("stats")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "importers", "path-to", "by-host"])
+          .help("Shows the module count and size per media type instead of the dependency tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-cache")
+          .long("show-cache")
+          .requires("module")
+          .help("Show whether each remote module was read from the cache or downloaded")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("exclude")
+          .long("exclude")
+          .requires("module")
+          .conflicts_with_all(["json", "dot", "mermaid", "npm-only"])
+          .value_name("GLOB")
+          .help("Hide the modules matching the glob from the tree. This flag can be passed multiple times")
+          .action(ArgAction::Append),
+      )
+      .arg(
+        Arg::new("exclude-from-totals")
+          .long("exclude-from-totals")
+          .requires("exclude")
+          .help("Don't count the modules hidden by --exclude in the dependency count and size")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("color")
+          .long("color")
+          .value_name("WHEN")
+          .value_parser(["auto", "always", "never"])
+          .help("When to use colors in the output"),
+      )
+      .arg(
+        Arg::new("subtree-size")
+          .long("subtree-size")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid"])
+          .help("Show the size of each module along with all the modules it transitively imports")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("flat")
+          .long("flat")
+          .requires("module")
+          .conflicts_with_all(["npm-only", "by-host", "stats"])
+          .help("Show a flat list of the unique modules instead of a tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-checksums")
+          .long("show-checksums")
+          .requires("module")
+          .help("Show the checksum of the source of each remote module")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("sizes")
+          .long("sizes")
+          .requires("json")
+          .conflicts_with("file")
+          .help("Include the disk usage of each cache directory in the JSON output. This can be slow for large caches")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("cache-usage")
+          .long("cache-usage")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "importers", "path-to", "by-host", "stats", "flat"])
+          .help("Show the files in the remote modules cache that aren't used by the module graph")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("roots-only")
+          .long("roots-only")
+          .requires("module")
+          .conflicts_with_all(["depth", "json", "dot", "mermaid", "npm-only", "flat"])
+          .help("Only show the direct dependencies of the root along with their total size")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("sbom")
+          .long("sbom")
+          .requires("module")
+          .conflicts_with_all(["json", "roots-only", "cache-usage"])
+          .help("Output a CycloneDX software bill of materials listing the npm packages and remote modules")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("color-by-type")
+          .long("color-by-type")
+          .requires("module")
+          .help("Color the modules in the tree by their type: TypeScript in blue, JavaScript in yellow, JSON in green, and npm packages in magenta")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("duplicates")
+          .long("duplicates")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "importers", "path-to", "by-host", "stats", "flat", "cache-usage", "sbom"])
+          .help("Shows the remote modules with identical content served from different URLs instead of the dependency tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("summary")
+          .long("summary")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "flat", "roots-only", "sbom"])
+          .help("Only show the totals of the module graph without the dependency tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("warn-size")
+          .long("warn-size")
+          .requires("module")
+          .value_name("SIZE")
+          .value_parser(size_arg_parse)
+          .help("Warn about the modules larger than the size, in bytes or with a unit like 500kb"),
+      )
+      .arg(
+        Arg::new("fail-on-warn")
+          .long("fail-on-warn")
+          .requires("warn-size")
+          .help("Exit with an error when a module is larger than --warn-size")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-emit")
+          .long("show-emit")
+          .requires("module")
+          .help("Mark the modules that are transpiled before they're run and show the path of their cached emit")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-config")
+          .long("show-config")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "flat", "sbom"])
+          .help("Show the compiler options in effect for the module graph")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("lock-check")
+          .long("lock-check")
+          .requires("module")
+          .conflicts_with("no-lock")
+          .help("Report the remote modules that differ from or are missing in the lock file instead of showing the graph. Exits with code 11 when there are differences")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-npm-urls")
+          .long("show-npm-urls")
+          .requires("module")
+          .help("Show the tarball URL that each npm package was downloaded from")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("diff")
+          .long("diff")
+          .requires("module")
+          .value_name("OTHER")
+          .value_hint(ValueHint::FilePath)
+          .help("Show the modules and npm packages that are added, removed, or changed in size in the graph of another module"),
+      )
+      .arg(
+        Arg::new("show-dep-counts")
+          .long("show-dep-counts")
+          .requires("module")
+          .help("Show the number of direct dependencies of each module in the tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("ascii")
+          .long("ascii")
+          .requires("module")
+          .help("Draw the tree with ASCII characters instead of box-drawing characters")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("no-type-deps")
+          .long("no-type-deps")
+          .requires("module")
+          .help("Hide the type-only dependencies and leave them out of the totals")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("why")
+          .long("why")
+          .requires("module")
+          .conflicts_with("json")
+          .value_name("PACKAGE")
+          .help("Shows the import chains that cause the npm package to be included (ex. npm:left-pad@1.3.0)"),
+      )
+      .arg(
+        Arg::new("csv")
+          .long("csv")
+          .requires("module")
+          .conflicts_with("json")
+          .help("Outputs the modules and npm packages as CSV with their kind, media type, size and dependency count")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("abbreviate")
+          .long("abbreviate")
+          .requires("module")
+          .conflicts_with_all(["json", "dot", "mermaid", "flat", "csv"])
+          .help("Shorten the shared prefixes of remote specifiers in the tree and show what they stand for at the bottom")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("max-nodes")
+          .long("max-nodes")
+          .requires("module")
+          .conflicts_with_all(["json", "dot", "mermaid", "flat", "csv"])
+          .value_name("N")
+          .value_parser(value_parser!(u32).range(1..))
+          .help("Stop displaying the dependency tree after this many nodes"),
+      )
+      .arg(
+        Arg::new("jsonl")
+          .long("jsonl")
+          .requires("module")
+          .conflicts_with_all([
+            "json", "dot", "mermaid", "flat", "csv", "summary", "npm-only",
+            "importers", "path-to", "why", "diff", "lock-check", "by-host",
+            "stats", "duplicates", "cache-usage", "sbom",
+          ])
+          .help("Outputs each module as a JSON object on its own line followed by a summary line")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-locations")
+          .long("show-locations")
+          .requires("module")
+          .help("Show where the direct dependencies of the root are imported and include the location of each import in the JSON output")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("percent")
+          .long("percent")
+          .requires("module")
+          .help("Show the size of each module and npm package as a percentage of the total size")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("top")
+          .long("top")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "importers", "path-to", "why", "by-host", "stats", "flat", "csv", "jsonl"])
+          .value_name("N")
+          .value_parser(value_parser!(u32).range(1..))
+          .help("Shows the N largest modules and npm packages instead of the dependency tree"),
+      )
+      .arg(
+        Arg::new("vendor-check")
+          .long("vendor-check")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "lock-check", "jsonl", "csv"])
+          .value_name("DIR")
+          .help("Report the remote modules that aren't loaded from the vendor directory instead of showing the graph. Exits with code 1 when there are any")
+          .value_parser(value_parser!(PathBuf))
+          .value_hint(ValueHint::DirPath),
+      )
+      .arg(
+        Arg::new("kind")
+          .long("kind")
+          .requires("module")
+          .value_parser(["all", "code-only", "types-only"])
+          .help("Which dependencies to include in the graph. code-only skips fetching the type declarations, which can make deno info much faster on type heavy projects"),
+      )
+      .arg(
+        Arg::new("relative")
+          .long("relative")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid", "csv"])
+          .help("Show local file specifiers in the tree relative to the current working directory")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("focus")
+          .long("focus")
+          .requires("module")
+          .conflicts_with_all(["lock-check", "vendor-check"])
+          .value_name("SPECIFIER")
+          .help("Show the graph as if the specified module was the root, so the totals only include its dependencies")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("npm-usage")
+          .long("npm-usage")
+          .requires("module")
+          .conflicts_with_all(["cache-usage", "jsonl"])
+          .help("Compare the size of each npm package to the size of its files that are reachable from the imports instead of showing the graph")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("count-only")
+          .long("count-only")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid", "flat", "csv", "summary", "show-config", "size-only"])
+          .help("Only print the number of unique dependencies, which is useful in scripts")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("size-only")
+          .long("size-only")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid", "flat", "csv", "summary", "show-config"])
+          .help("Only print the total size of the dependencies in bytes, which is useful in scripts")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("verbose")
+          .long("verbose")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid"])
+          .help("Print how long building the graph took and how many remote modules were downloaded to stderr after the tree")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("format")
+          .long("format")
+          .requires("flat")
+          .conflicts_with("json")
+          .value_name("TEMPLATE")
+          .help("Template of each line of the flat list. Supports {specifier}, {size} in bytes, {media_type} and {dependency_count}, and {{ and }} for braces")
+          .value_parser(info_format_parse),
+      )
+      .arg(
+        Arg::new("group-npm-by-root")
+          .long("group-npm-by-root")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid", "flat", "csv", "no-npm", "npm-only"])
+          .help("Only show the npm packages, grouped under the directly imported package that pulled them in")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("fail-on-insecure")
+          .long("fail-on-insecure")
+          .requires("module")
+          .help("Exit with an error when a module is imported over plain http")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("resolve-only")
+          .long("resolve-only")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "jsonl", "importers", "path-to"])
+          .value_name("SPECIFIER")
+          .help("Show what the specifier resolves to in the graph, along with its type, size and local path, instead of showing the graph")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("treemap-json")
+          .long("treemap-json")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl"])
+          .help("Output the dependency tree as nested JSON nodes with a name, a value of the module's own size, and children, for treemap visualizations")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("split-sizes")
+          .long("split-sizes")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "flat", "csv"])
+          .help("Split the total size into the size of the root, of the modules it depends on, and of the npm packages")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("no-dedup")
+          .long("no-dedup")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid", "flat", "verbose-dedup"])
+          .help("Expand modules and npm packages every time they appear instead of only the first time. The output can be very large")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("width")
+          .long("width")
+          .requires("module")
+          .value_name("N")
+          .value_parser(value_parser!(u32))
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid"])
+          .help("Shorten the lines of the tree that are wider than N columns. Defaults to the width of the terminal"),
+      )
+      .arg(
+        Arg::new("runtime-only")
+          .long("runtime-only")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "flat", "csv"])
+          .help("Only show the modules that are loaded at runtime, leaving out the modules that are only reachable through type-only dependencies and the @types npm packages")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("legend")
+          .long("legend")
+          .requires("module")
+          .conflicts_with_all(["json", "jsonl", "dot", "mermaid", "flat", "csv"])
+          .help("Explain the connectors, markers and colors of the tree after it. Also shown with --verbose")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("cache-only")
+          .long("cache-only")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "lock-check", "vendor-check", "jsonl", "csv", "watch"])
+          .help("Build the graph only from the local cache and report the modules that aren't cached instead of showing the graph. Nothing is downloaded. Exits with code 1 when there are any")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("show-import-map")
+          .long("show-import-map")
+          .requires("module")
+          .conflicts_with_all(["dot", "mermaid", "flat", "csv"])
+          .help("Show the import map and the entries of it that were used to resolve the modules")
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        watch_arg(false)
+          .requires("module")
+          .conflicts_with("lock-check"),
+      )
+      .arg(no_clear_screen_arg())
+      // each of these replaces the tree with another output
+      .group(
+        ArgGroup::new("output-mode")
+          .args([
+            "dot", "mermaid", "csv", "sbom", "flat", "treemap-json", "diff",
+            "npm-usage", "why", "importers", "path-to",
+          ])
+          .multiple(false),
       ))
 }
 
@@ -2806,9 +3527,172 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
   let json = matches.get_flag("json");
+  let dot = matches.get_flag("dot");
+  let mermaid = matches.get_flag("mermaid");
+  let depth = matches.remove_one::<u32>("depth");
+  let npm_only = matches.get_flag("npm-only");
+  let sort = match matches.remove_one::<String>("sort").as_deref() {
+    Some("size") => InfoSortOrder::Size,
+    _ => InfoSortOrder::Name,
+  };
+  let show_licenses = matches.get_flag("show-licenses");
+  let show_types = matches.get_flag("show-types");
+  let compact = matches.get_flag("compact");
+  let importers = matches.remove_one::<String>("importers");
+  let path_to = matches.remove_one::<String>("path-to");
+  let by_host = matches.get_flag("by-host");
+  let out = matches.remove_one::<PathBuf>("out");
+  let compressed = matches.get_flag("compressed");
+  let verbose_dedup = matches.get_flag("verbose-dedup");
+  let filter = matches.remove_one::<String>("filter");
+  let stats = matches.get_flag("stats");
+  let show_cache = matches.get_flag("show-cache");
+  let exclude = match matches.remove_many::<String>("exclude") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  let exclude_from_totals = matches.get_flag("exclude-from-totals");
+  let color = match matches.remove_one::<String>("color").as_deref() {
+    Some("always") => InfoColorMode::Always,
+    Some("never") => InfoColorMode::Never,
+    _ => InfoColorMode::Auto,
+  };
+  let subtree_size = matches.get_flag("subtree-size");
+  let flat = matches.get_flag("flat");
+  let show_checksums = matches.get_flag("show-checksums");
+  let sizes = matches.get_flag("sizes");
+  let cache_usage = matches.get_flag("cache-usage");
+  let roots_only = matches.get_flag("roots-only");
+  let sbom = matches.get_flag("sbom");
+  let color_by_type = matches.get_flag("color-by-type");
+  let duplicates = matches.get_flag("duplicates");
+  let summary = matches.get_flag("summary");
+  let warn_size = matches.remove_one::<u64>("warn-size");
+  let fail_on_warn = matches.get_flag("fail-on-warn");
+  let show_emit = matches.get_flag("show-emit");
+  let show_config = matches.get_flag("show-config");
+  let lock_check = matches.get_flag("lock-check");
+  let show_npm_urls = matches.get_flag("show-npm-urls");
+  let diff = matches.remove_one::<String>("diff");
+  let show_dep_counts = matches.get_flag("show-dep-counts");
+  let ascii = matches.get_flag("ascii");
+  let no_type_deps = matches.get_flag("no-type-deps");
+  let watch = watch_arg_parse(matches);
+  let why = matches.remove_one::<String>("why");
+  let csv = matches.get_flag("csv");
+  let abbreviate = matches.get_flag("abbreviate");
+  let max_nodes = matches.remove_one::<u32>("max-nodes");
+  let jsonl = matches.get_flag("jsonl");
+  let show_locations = matches.get_flag("show-locations");
+  let percent = matches.get_flag("percent");
+  let top = matches.remove_one::<u32>("top");
+  let vendor_check = matches.remove_one::<PathBuf>("vendor-check");
+  let kind = match matches.remove_one::<String>("kind").as_deref() {
+    Some("code-only") => InfoGraphKind::CodeOnly,
+    Some("types-only") => InfoGraphKind::TypesOnly,
+    _ => InfoGraphKind::All,
+  };
+  let relative = matches.get_flag("relative");
+  let focus = matches.remove_one::<String>("focus");
+  let npm_usage = matches.get_flag("npm-usage");
+  let count_only = matches.get_flag("count-only");
+  let size_only = matches.get_flag("size-only");
+  let verbose = matches.get_flag("verbose");
+  let format = matches.remove_one::<Vec<InfoFormatToken>>("format");
+  let group_npm_by_root = matches.get_flag("group-npm-by-root");
+  let fail_on_insecure = matches.get_flag("fail-on-insecure");
+  let resolve_only = matches.remove_one::<String>("resolve-only");
+  let treemap_json = matches.get_flag("treemap-json");
+  let split_sizes = matches.get_flag("split-sizes");
+  let no_dedup = matches.get_flag("no-dedup");
+  let show_import_map = matches.get_flag("show-import-map");
+  let entrypoint_from_config = matches.get_flag("entrypoint-from-config");
+  let width = matches.remove_one::<u32>("width");
+  let runtime_only = matches.get_flag("runtime-only");
+  let legend = matches.get_flag("legend");
+  let cache_only = matches.get_flag("cache-only");
+  if cache_only {
+    // makes the file fetcher and the npm registry fail on anything that
+    // isn't cached instead of downloading it
+    flags.cached_only = true;
+  }
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
-    file: matches.remove_one::<String>("file"),
+    file: matches
+      .remove_many::<String>("file")
+      .map(|files| files.collect())
+      .unwrap_or_default(),
     json,
+    dot,
+    mermaid,
+    depth,
+    npm_only,
+    sort,
+    show_licenses,
+    show_types,
+    compact,
+    importers,
+    path_to,
+    by_host,
+    no_npm: flags.no_npm,
+    out,
+    compressed,
+    verbose_dedup,
+    filter,
+    stats,
+    show_cache,
+    exclude,
+    exclude_from_totals,
+    color,
+    subtree_size,
+    flat,
+    show_checksums,
+    sizes,
+    cache_usage,
+    roots_only,
+    sbom,
+    color_by_type,
+    duplicates,
+    summary,
+    warn_size,
+    fail_on_warn,
+    show_emit,
+    show_config,
+    lock_check,
+    show_npm_urls,
+    diff,
+    show_dep_counts,
+    ascii,
+    no_type_deps,
+    watch,
+    why,
+    csv,
+    abbreviate,
+    max_nodes,
+    jsonl,
+    show_locations,
+    percent,
+    top,
+    vendor_check,
+    kind,
+    relative,
+    focus,
+    npm_usage,
+    count_only,
+    size_only,
+    verbose,
+    format,
+    group_npm_by_root,
+    fail_on_insecure,
+    resolve_only,
+    treemap_json,
+    split_sizes,
+    no_dedup,
+    show_import_map,
+    entrypoint_from_config,
+    width,
+    runtime_only,
+    legend,
+    cache_only,
   });
 }
 
@@ -3351,6 +4235,84 @@ fn reload_arg_validate(urlstr: &str) -> Result<String, String> {
   }
 }
 
+/// Parses a size in bytes, optionally followed by a `b`, `kb`, `mb`, or `gb`
+/// unit (ex. `500kb`).
+fn size_arg_parse(size: &str) -> Result<u64, String> {
+  let size = size.trim().to_ascii_lowercase();
+  let unit_start = size
+    .find(|c: char| !c.is_ascii_digit() && c != '.')
+    .unwrap_or(size.len());
+  let (number, unit) = size.split_at(unit_start);
+  let multiplier = match unit.trim() {
+    "" | "b" => 1,
+    "kb" => 1024,
+    "mb" => 1024 * 1024,
+    "gb" => 1024 * 1024 * 1024,
+    unit => {
+      return Err(format!(
+        "Invalid size unit '{unit}'. Expected b, kb, mb, or gb."
+      ))
+    }
+  };
+  match number.parse::<f64>() {
+    Ok(number) => Ok((number * multiplier as f64) as u64),
+    Err(_) => Err(format!("Invalid size '{size}'.")),
+  }
+}
+
+/// Parses a `--format` template into the text and the placeholders it's made
+/// of, so unknown placeholders are reported before building the graph.
+fn info_format_parse(template: &str) -> Result<Vec<InfoFormatToken>, String> {
+  let mut tokens = Vec::new();
+  let mut text = String::new();
+  let mut chars = template.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '{' if chars.peek() == Some(&'{') => {
+        chars.next();
+        text.push('{');
+      }
+      '}' if chars.peek() == Some(&'}') => {
+        chars.next();
+        text.push('}');
+      }
+      '{' => {
+        let mut name = String::new();
+        loop {
+          match chars.next() {
+            Some('}') => break,
+            Some(c) => name.push(c),
+            None => {
+              return Err(format!("Unclosed placeholder '{{{name}'."));
+            }
+          }
+        }
+        let token = match name.as_str() {
+          "specifier" => InfoFormatToken::Specifier,
+          "size" => InfoFormatToken::Size,
+          "media_type" => InfoFormatToken::MediaType,
+          "dependency_count" => InfoFormatToken::DependencyCount,
+          _ => {
+            return Err(format!(
+              "Unknown placeholder '{{{name}}}'. Expected {{specifier}}, {{size}}, {{media_type}}, or {{dependency_count}}."
+            ))
+          }
+        };
+        if !text.is_empty() {
+          tokens.push(InfoFormatToken::Text(std::mem::take(&mut text)));
+        }
+        tokens.push(token);
+      }
+      '}' => return Err("Unmatched '}'. Use '}}' for a brace.".to_string()),
+      c => text.push(c),
+    }
+  }
+  if !text.is_empty() {
+    tokens.push(InfoFormatToken::Text(text));
+  }
+  Ok(tokens)
+}
+
 fn watch_arg_parse(matches: &mut ArgMatches) -> Option<WatchFlags> {
   if matches.get_flag("watch") {
     Some(WatchFlags {
@@ -4317,7 +5279,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: Some("script.ts".to_string()),
+          file: vec!["script.ts".to_string()],
+          ..Default::default()
         }),
         ..Flags::default()
       }
@@ -4329,7 +5292,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: Some("script.ts".to_string()),
+          file: vec!["script.ts".to_string()],
+          ..Default::default()
         }),
         reload: true,
         ..Flags::default()
@@ -4342,7 +5306,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
-          file: Some("script.ts".to_string()),
+          file: vec!["script.ts".to_string()],
+          ..Default::default()
         }),
         ..Flags::default()
       }
@@ -4354,7 +5319,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: vec![],
+          ..Default::default()
         }),
         ..Flags::default()
       }
@@ -4366,7 +5332,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
-          file: None
+          file: vec![],
+          ..Default::default()
         }),
         ..Flags::default()
       }
@@ -4385,7 +5352,9 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: vec![],
+          no_npm: true,
+          ..Default::default()
         }),
         config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
         no_npm: true,
@@ -4395,6 +5364,1642 @@ mod tests {
     );
   }
 
+  #[test]
+  fn info_dot() {
+    let r = flags_from_vec(svec!["deno", "info", "--dot", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          dot: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--dot", "--json", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_mermaid() {
+    let r = flags_from_vec(svec!["deno", "info", "--mermaid", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          mermaid: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--mermaid", "--dot", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_depth() {
+    let r = flags_from_vec(svec!["deno", "info", "--depth=2", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          depth: Some(2),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_npm_only() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--npm-only", "--json", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["main.ts".to_string()],
+          json: true,
+          npm_only: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_sort() {
+    let r = flags_from_vec(svec!["deno", "info", "--sort=size", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          sort: InfoSortOrder::Size,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--sort=date", "script.ts"]);
+    assert_eq!(r.unwrap_err().kind(), clap::error::ErrorKind::InvalidValue);
+  }
+
+  #[test]
+  fn info_show_licenses() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--show-licenses", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_licenses: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_show_types() {
+    let r = flags_from_vec(svec!["deno", "info", "--show-types", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_types: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_compact() {
+    let r = flags_from_vec(svec!["deno", "info", "--json", "--compact"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          compact: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--compact", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::MissingRequiredArgument
+    );
+  }
+
+  #[test]
+  fn info_importers() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--importers",
+      "./common.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          importers: Some("./common.ts".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_path_to() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--path-to",
+      "./common.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          path_to: Some("./common.ts".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--json",
+      "--path-to",
+      "./common.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_by_host() {
+    let r = flags_from_vec(svec!["deno", "info", "--by-host", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          by_host: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--by-host"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::MissingRequiredArgument
+    );
+  }
+
+  #[test]
+  fn info_out() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--json",
+      "--out",
+      "graph.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          json: true,
+          out: Some(PathBuf::from("graph.json")),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_compressed() {
+    let r = flags_from_vec(svec!["deno", "info", "--compressed", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          compressed: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_verbose_dedup() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--verbose-dedup", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          verbose_dedup: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_filter() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--filter",
+      "https://deno.land/std/*",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          filter: Some("https://deno.land/std/*".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_stats() {
+    let r = flags_from_vec(svec!["deno", "info", "--stats", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          stats: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--stats",
+      "--by-host",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_show_cache() {
+    let r = flags_from_vec(svec!["deno", "info", "--show-cache", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_cache: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_exclude() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--exclude",
+      "*.json",
+      "--exclude",
+      "*/vendor/*",
+      "--exclude-from-totals",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          exclude: svec!["*.json", "*/vendor/*"],
+          exclude_from_totals: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--exclude-from-totals",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::MissingRequiredArgument
+    );
+  }
+
+  #[test]
+  fn info_color() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--color=always", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          color: InfoColorMode::Always,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--color", "never"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          color: InfoColorMode::Never,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--color=sometimes"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_subtree_size() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--subtree-size", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          subtree_size: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_flat() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--flat",
+      "--sort=size",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          flat: true,
+          sort: InfoSortOrder::Size,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--flat", "--dot", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_show_checksums() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--show-checksums", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_checksums: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_sizes() {
+    let r = flags_from_vec(svec!["deno", "info", "--json", "--sizes"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: true,
+          sizes: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--sizes"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::MissingRequiredArgument
+    );
+  }
+
+  #[test]
+  fn info_cache_usage() {
+    let r = flags_from_vec(svec!["deno", "info", "--cache-usage", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          cache_usage: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--cache-usage"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::MissingRequiredArgument
+    );
+  }
+
+  #[test]
+  fn info_roots_only() {
+    let r = flags_from_vec(svec!["deno", "info", "--roots-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          roots_only: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--roots-only",
+      "--depth=2",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_stdin() {
+    let r = flags_from_vec(svec!["deno", "info", "-"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["-".to_string()],
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_sbom() {
+    let r = flags_from_vec(svec!["deno", "info", "--sbom", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          sbom: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--sbom", "--json", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--sbom", "--flat", "script.ts"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--sbom",
+      "--diff",
+      "other.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_color_by_type() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--color-by-type", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          color_by_type: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_duplicates() {
+    let r = flags_from_vec(svec!["deno", "info", "--duplicates", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          duplicates: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_summary() {
+    let r = flags_from_vec(svec!["deno", "info", "--summary", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          summary: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_warn_size() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--warn-size=500kb",
+      "--fail-on-warn",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          warn_size: Some(500 * 1024),
+          fail_on_warn: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--warn-size=10tb", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_show_emit() {
+    let r = flags_from_vec(svec!["deno", "info", "--show-emit", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_emit: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_show_config() {
+    let r = flags_from_vec(svec!["deno", "info", "--show-config", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_config: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--show-config",
+      "--dot",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_lock_check() {
+    let r = flags_from_vec(svec!["deno", "info", "--lock-check", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          lock_check: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--lock-check",
+      "--no-lock",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_show_npm_urls() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--show-npm-urls", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_npm_urls: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_diff() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--diff", "other.ts", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          diff: Some("other.ts".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--diff",
+      "other.ts",
+      "--dot",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--diff",
+      "other.ts",
+      "--why",
+      "left-pad",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_show_dep_counts() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--show-dep-counts", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_dep_counts: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_ascii() {
+    let r = flags_from_vec(svec!["deno", "info", "--ascii", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          ascii: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_no_type_deps() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--no-type-deps", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          no_type_deps: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_watch() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--watch",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          watch: Some(WatchFlags {
+            no_clear_screen: true,
+          }),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--watch"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_why() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--why",
+      "npm:left-pad@1.3.0",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          why: Some("npm:left-pad@1.3.0".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--why",
+      "left-pad",
+      "--path-to",
+      "./common.ts",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_csv() {
+    let r = flags_from_vec(svec!["deno", "info", "--csv", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          csv: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--csv", "--json", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_abbreviate() {
+    let r = flags_from_vec(svec!["deno", "info", "--abbreviate", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          abbreviate: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_max_nodes() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--max-nodes", "50", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          max_nodes: Some(50),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--max-nodes", "0", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_jsonl() {
+    let r = flags_from_vec(svec!["deno", "info", "--jsonl", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          jsonl: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--jsonl", "--json", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_show_locations() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--show-locations", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_locations: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_percent() {
+    let r = flags_from_vec(svec!["deno", "info", "--percent", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          percent: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_top() {
+    let r = flags_from_vec(svec!["deno", "info", "--top=10", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          top: Some(10),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--top=10", "--stats", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_vendor_check() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--vendor-check",
+      "vendor",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          vendor_check: Some(PathBuf::from("vendor")),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_kind() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--kind=code-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          kind: InfoGraphKind::CodeOnly,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--kind=types-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          kind: InfoGraphKind::TypesOnly,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--kind=other", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_relative() {
+    let r = flags_from_vec(svec!["deno", "info", "--relative", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          relative: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--relative"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_focus() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--focus",
+      "./lib/mod.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          focus: Some("./lib/mod.ts".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_npm_usage() {
+    let r = flags_from_vec(svec!["deno", "info", "--npm-usage", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          npm_usage: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--npm-usage",
+      "--cache-usage",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--npm-usage",
+      "--sbom",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--npm-usage",
+      "--why",
+      "left-pad",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--npm-usage",
+      "--path-to",
+      "./common.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_count_only() {
+    let r = flags_from_vec(svec!["deno", "info", "--count-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          count_only: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--size-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          size_only: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--count-only",
+      "--size-only",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_verbose() {
+    let r = flags_from_vec(svec!["deno", "info", "--verbose", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          verbose: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--verbose", "--json", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_format() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--flat",
+      "--format={specifier}\t{size}",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          flat: true,
+          format: Some(vec![
+            InfoFormatToken::Specifier,
+            InfoFormatToken::Text("\t".to_string()),
+            InfoFormatToken::Size,
+          ]),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--flat",
+      "--format={name}",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r =
+      flags_from_vec(svec!["deno", "info", "--format={size}", "script.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_format_parse_braces() {
+    assert_eq!(
+      info_format_parse("{{{media_type}}}"),
+      Ok(vec![
+        InfoFormatToken::Text("{".to_string()),
+        InfoFormatToken::MediaType,
+        InfoFormatToken::Text("}".to_string()),
+      ])
+    );
+    assert_eq!(
+      info_format_parse("{dependency_count} deps"),
+      Ok(vec![
+        InfoFormatToken::DependencyCount,
+        InfoFormatToken::Text(" deps".to_string()),
+      ])
+    );
+    assert!(info_format_parse("{size").is_err());
+    assert!(info_format_parse("size}").is_err());
+  }
+
+  #[test]
+  fn info_group_npm_by_root() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--group-npm-by-root", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          group_npm_by_root: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--group-npm-by-root",
+      "--no-npm",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_fail_on_insecure() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--fail-on-insecure", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          fail_on_insecure: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_resolve_only() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--resolve-only",
+      "./mod.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          resolve_only: Some("./mod.ts".to_string()),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_treemap_json() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--treemap-json", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          treemap_json: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--treemap-json",
+      "--json",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--treemap-json",
+      "--sbom",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--treemap-json",
+      "--importers",
+      "./common.ts",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
+  #[test]
+  fn info_split_sizes() {
+    let r = flags_from_vec(svec!["deno", "info", "--split-sizes", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          split_sizes: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_multiple_files() {
+    let r = flags_from_vec(svec!["deno", "info", "a.ts", "b.ts", "c.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: svec!["a.ts", "b.ts", "c.ts"],
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_no_dedup() {
+    let r = flags_from_vec(svec!["deno", "info", "--no-dedup", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          no_dedup: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--no-dedup",
+      "--json",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_show_import_map() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--show-import-map",
+      "--import-map=import_map.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          show_import_map: true,
+          ..Default::default()
+        }),
+        import_map_path: Some("import_map.json".to_owned()),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_entrypoint_from_config() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--entrypoint-from-config",
+      "--json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          entrypoint_from_config: true,
+          json: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--entrypoint-from-config",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn info_width() {
+    let r = flags_from_vec(svec!["deno", "info", "--width=60", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          width: Some(60),
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_runtime_only() {
+    let r =
+      flags_from_vec(svec!["deno", "info", "--runtime-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          runtime_only: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_legend() {
+    let r = flags_from_vec(svec!["deno", "info", "--legend", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          legend: true,
+          ..Default::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn info_cache_only() {
+    let r = flags_from_vec(svec!["deno", "info", "--cache-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          file: vec!["script.ts".to_string()],
+          cache_only: true,
+          ..Default::default()
+        }),
+        cached_only: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn size_arg_parse_units() {
+    assert_eq!(size_arg_parse("100"), Ok(100));
+    assert_eq!(size_arg_parse("100b"), Ok(100));
+    assert_eq!(size_arg_parse("2KB"), Ok(2048));
+    assert_eq!(size_arg_parse("1.5mb"), Ok(1572864));
+    assert_eq!(size_arg_parse("1gb"), Ok(1073741824));
+    assert!(size_arg_parse("kb").is_err());
+    assert!(size_arg_parse("1.2.3kb").is_err());
+  }
+
   #[test]
   fn tsconfig() {
     let r =
@@ -5129,8 +7734,9 @@ mod tests {
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
-          file: Some("script.ts".to_string()),
+          file: vec!["script.ts".to_string()],
           json: false,
+          ..Default::default()
         }),
         import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
@@ -6222,7 +8828,8 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: Some("https://example.com".to_string()),
+          file: vec!["https://example.com".to_string()],
+          ..Default::default()
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
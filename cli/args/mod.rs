@@ -509,6 +509,33 @@ pub fn get_root_cert_store(
   Ok(root_cert_store)
 }
 
+/// Reads the url of a package registry from an environment variable, falling
+/// back to the default url when it's not set or isn't a valid url.
+pub fn registry_url_from_env(env_var_name: &str, default_url: &str) -> Url {
+  if let Ok(registry_url) = env::var(env_var_name) {
+    // ensure there is a trailing slash for the directory
+    let registry_url = format!("{}/", registry_url.trim_end_matches('/'));
+    match Url::parse(&registry_url) {
+      Ok(url) => {
+        return url;
+      }
+      Err(err) => {
+        log::debug!("Invalid {} environment variable: {:#}", env_var_name, err);
+      }
+    }
+  }
+
+  Url::parse(default_url).unwrap()
+}
+
+static JSR_URL: Lazy<Url> =
+  Lazy::new(|| registry_url_from_env("JSR_URL", "https://jsr.io/"));
+
+/// The url of the JSR registry, which is overridden with `JSR_URL` in tests.
+pub fn jsr_url() -> &'static Url {
+  &JSR_URL
+}
+
 const RESOLUTION_STATE_ENV_VAR_NAME: &str =
   "DENO_DONT_USE_INTERNAL_NODE_COMPAT_STATE";
 
@@ -35,6 +35,7 @@ use deno_runtime::permissions::PermissionsContainer;
 use log::debug;
 use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::env;
 use std::fs;
 use std::future::Future;
@@ -182,6 +183,9 @@ pub struct FileFetcher {
   blob_store: BlobStore,
   download_log_level: log::Level,
   progress_bar: Option<ProgressBar>,
+  /// Remote specifiers whose source was downloaded instead of being read
+  /// from the HTTP cache.
+  downloaded: Arc<Mutex<HashSet<ModuleSpecifier>>>,
 }
 
 impl FileFetcher {
@@ -203,6 +207,7 @@ impl FileFetcher {
       blob_store,
       download_log_level: log::Level::Info,
       progress_bar,
+      downloaded: Default::default(),
     }
   }
 
@@ -491,6 +496,7 @@ impl FileFetcher {
           file_fetcher
             .http_cache
             .set(&specifier, headers.clone(), &bytes)?;
+          file_fetcher.downloaded.lock().insert(specifier.clone());
           let file =
             file_fetcher.build_remote_file(&specifier, bytes, &headers)?;
           Ok(file)
@@ -594,6 +600,12 @@ impl FileFetcher {
     }
   }
 
+  /// Gets if the source of the remote specifier was downloaded by this file
+  /// fetcher rather than read from the HTTP cache.
+  pub fn was_downloaded(&self, specifier: &ModuleSpecifier) -> bool {
+    self.downloaded.lock().contains(specifier)
+  }
+
   pub fn get_local_path(&self, specifier: &ModuleSpecifier) -> Option<PathBuf> {
     // TODO(@kitsonk) fix when deno_graph does not query cache for synthetic
     // modules
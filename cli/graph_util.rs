@@ -12,6 +12,7 @@ use crate::npm::CliNpmResolver;
 use crate::resolver::CliGraphResolver;
 use crate::tools::check;
 use crate::tools::check::TypeChecker;
+use crate::util::checksum;
 
 use deno_core::anyhow::bail;
 use deno_core::error::custom_error;
@@ -164,6 +165,54 @@ pub fn graph_lock_or_exit(graph: &ModuleGraph, lockfile: &mut Lockfile) {
   }
 }
 
+/// The remote modules of a graph that don't match the lockfile.
+#[derive(Debug, Default)]
+pub struct LockfileDiff {
+  /// Modules whose source changed, along with the checksum from the lockfile
+  /// and the checksum of the current source.
+  pub mismatched: Vec<(ModuleSpecifier, String, String)>,
+  /// Modules that aren't in the lockfile.
+  pub missing: Vec<ModuleSpecifier>,
+}
+
+impl LockfileDiff {
+  pub fn is_empty(&self) -> bool {
+    self.mismatched.is_empty() && self.missing.is_empty()
+  }
+}
+
+/// Compares the graph against the lockfile without modifying it, unlike
+/// `graph_lock_or_exit`.
+pub fn graph_lock_diff(
+  graph: &ModuleGraph,
+  lockfile: &Lockfile,
+) -> LockfileDiff {
+  let mut diff = LockfileDiff::default();
+  for module in graph.modules() {
+    let source = match module {
+      Module::Esm(module) => &module.source,
+      Module::Json(module) => &module.source,
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => continue,
+    };
+    let specifier = module.specifier();
+    // the lockfile only stores the checksums of remote modules
+    if !matches!(specifier.scheme(), "http" | "https") {
+      continue;
+    }
+    let actual = checksum::gen(&[source.as_bytes()]);
+    match lockfile.content.remote.get(specifier.as_str()) {
+      Some(expected) if *expected == actual => {}
+      Some(expected) => {
+        diff
+          .mismatched
+          .push((specifier.clone(), expected.clone(), actual));
+      }
+      None => diff.missing.push(specifier.clone()),
+    }
+  }
+  diff
+}
+
 pub struct ModuleGraphBuilder {
   options: Arc<CliOptions>,
   resolver: Arc<CliGraphResolver>,
@@ -246,6 +295,42 @@ impl ModuleGraphBuilder {
     Ok(graph)
   }
 
+  /// Creates a graph like `create_graph_with_loader`, but only resolves the
+  /// npm packages instead of also caching them, so the packages that aren't
+  /// cached yet can be found without failing.
+  pub async fn create_graph_without_npm_caching(
+    &self,
+    graph_kind: GraphKind,
+    roots: Vec<ModuleSpecifier>,
+    loader: &mut dyn Loader,
+  ) -> Result<deno_graph::ModuleGraph, AnyError> {
+    let maybe_imports = self.options.to_maybe_imports()?;
+
+    let cli_resolver = self.resolver.clone();
+    let graph_resolver = cli_resolver.as_graph_resolver();
+    let graph_npm_resolver = cli_resolver.as_graph_npm_resolver();
+    let analyzer = self.parsed_source_cache.as_analyzer();
+
+    let mut graph = ModuleGraph::new(graph_kind);
+    graph
+      .build(
+        roots,
+        loader,
+        deno_graph::BuildOptions {
+          is_dynamic: false,
+          imports: maybe_imports,
+          resolver: Some(graph_resolver),
+          npm_resolver: Some(graph_npm_resolver),
+          module_analyzer: Some(&*analyzer),
+          reporter: None,
+        },
+      )
+      .await;
+    self.npm_resolver.resolve_pending_without_caching().await?;
+
+    Ok(graph)
+  }
+
   pub async fn create_graph_and_maybe_check(
     &self,
     roots: Vec<ModuleSpecifier>,
@@ -235,6 +235,11 @@ fn unwrap_or_exit<T>(result: Result<T, AnyError>) -> T {
       } else if let Some(e) = error.downcast_ref::<args::LockfileError>() {
         error_string = e.to_string();
         error_code = 10;
+      } else if let Some(e) =
+        error.downcast_ref::<tools::info::LockCheckError>()
+      {
+        error_string = e.to_string();
+        error_code = tools::info::LOCK_CHECK_EXIT_CODE;
       }
 
       eprintln!(
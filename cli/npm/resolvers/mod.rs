@@ -154,6 +154,19 @@ impl CliNpmResolver {
     Ok(crate::util::fs::dir_size(&package_folder)?)
   }
 
+  /// Reads the package.json of the provided package as raw JSON.
+  pub fn package_json_value(
+    &self,
+    package_id: &NpmPackageId,
+  ) -> Result<serde_json::Value, AnyError> {
+    let package_folder = self.fs_resolver.package_folder(package_id)?;
+    let source = self
+      .fs
+      .read_to_string(&package_folder.join("package.json"))
+      .map_err(|err| err.into_io_error())?;
+    Ok(serde_json::from_str(&source)?)
+  }
+
   /// Gets if the provided specifier is in an npm package.
   pub fn in_npm_package(&self, specifier: &ModuleSpecifier) -> bool {
     let root_dir_url = self.fs_resolver.root_dir_url();
@@ -236,6 +249,12 @@ impl CliNpmResolver {
     self.fs_resolver.cache_packages().await?;
     Ok(())
   }
+
+  /// Resolves the pending package requirements without caching any
+  /// package files.
+  pub async fn resolve_pending_without_caching(&self) -> Result<(), AnyError> {
+    self.resolution.resolve_pending().await
+  }
 }
 
 impl NpmResolver for CliNpmResolver {
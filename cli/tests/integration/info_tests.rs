@@ -1,7 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
 use test_util as util;
 use test_util::TempDir;
+use test_util::TestContextBuilder;
 use util::env_vars_for_npm_tests_no_sync_download;
 
 #[test]
@@ -35,12 +37,323 @@ fn info_with_compiled_source() {
   assert_eq!(output.stderr, b"");
 }
 
+#[test]
+fn info_out_file() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "import './mod.ts';\n");
+  temp_dir.write("mod.ts", "export const value = 1;\n");
+
+  let output = context
+    .new_command()
+    .args("info --quiet --json --out graph.json main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("");
+
+  let json: serde_json::Value =
+    serde_json::from_str(&temp_dir.read_to_string("graph.json")).unwrap();
+  assert_eq!(json["modules"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn info_out_file_tree() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "import './mod.ts';\n");
+  temp_dir.write("mod.ts", "export const value = 1;\n");
+
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("info --quiet --out tree.txt main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("");
+
+  let tree = temp_dir.read_to_string("tree.txt");
+  assert!(tree.contains("dependencies: 1 unique"));
+  assert!(tree.contains("└── file:///"));
+  assert!(tree.trim_end().ends_with("mod.ts (24B)"));
+}
+
+#[test]
+fn info_color() {
+  let context = TestContextBuilder::new().build();
+  // NO_COLOR is set for the test commands, so colors only show when forced
+  let output = context
+    .new_command()
+    .args("info --quiet --color=always info/info_recursive_imports_test.ts")
+    .run();
+  output.assert_exit_code(0);
+  assert!(output.combined_output().contains("\x1b["));
+}
+
+#[test]
+fn info_verbose() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_separate_deno_dir()
+    .build();
+  let args = "info --quiet --verbose run/type_directives_01.ts";
+  let output = context.new_command().args(args).split_output().run();
+  output.assert_exit_code(0);
+  assert!(!output.stdout().contains("graph built in:"));
+  assert!(output.stderr().contains("graph built in: "));
+  assert!(output
+    .stderr()
+    .contains("remote modules: 2 downloaded, 0 from the cache"));
+
+  // the second run is served from the cache
+  let output = context.new_command().args(args).split_output().run();
+  output.assert_exit_code(0);
+  assert!(output
+    .stderr()
+    .contains("remote modules: 0 downloaded, 2 from the cache"));
+}
+
+#[test]
+fn info_color_by_type() {
+  let context = TestContextBuilder::new().build();
+  let output = context
+    .new_command()
+    .args("info --quiet --color=always --color-by-type info/info_recursive_imports_test.ts")
+    .run();
+  output.assert_exit_code(0);
+  // TypeScript modules are shown in blue
+  assert!(output.combined_output().contains("\x1b[34m"));
+}
+
+#[test]
+fn info_cache_usage() {
+  let context = TestContextBuilder::new().use_http_server().build();
+  let output = context
+    .new_command()
+    .args("info --quiet --cache-usage http://localhost:4545/subdir/mod2.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("orphaned files: 0\nreclaimable size: 0B\n");
+
+  // mod2.ts is no longer used when only looking at what it imports
+  let output = context
+    .new_command()
+    .args(
+      "info --quiet --cache-usage http://localhost:4545/subdir/print_hello.ts",
+    )
+    .run();
+  output.assert_exit_code(0);
+  output
+    .assert_matches_text("orphaned files: 1\nreclaimable size: [WILDCARD]\n");
+}
+
+#[test]
+fn info_json_dynamic_import() {
+  let context = TestContextBuilder::new().build();
+  let output = context
+    .new_command()
+    .args("info --quiet --json info/dynamic_import/main.ts")
+    .run();
+  output.assert_exit_code(0);
+
+  let json: serde_json::Value =
+    serde_json::from_str(output.combined_output()).unwrap();
+  let main = json["modules"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|module| module["specifier"].as_str().unwrap().ends_with("/main.ts"))
+    .unwrap();
+  let dependencies = main["dependencies"].as_array().unwrap();
+  assert_eq!(dependencies[0]["specifier"], "./static.ts");
+  assert_eq!(dependencies[0].get("dynamic"), None);
+  assert_eq!(dependencies[1]["specifier"], "./dynamic.ts");
+  assert_eq!(dependencies[1]["dynamic"], true);
+  assert_eq!(json["dynamicImports"], 1);
+}
+
+#[test]
+fn info_stdin() {
+  let context = TestContextBuilder::new().build();
+  let output = context
+    .new_command()
+    .args("info --quiet -")
+    .stdin("info/info_recursive_imports_test.ts\n")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_file("info/info_recursive_imports_test.out");
+
+  let output = context.new_command().args("info --quiet -").stdin("").run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "error: Expected a module specifier on stdin, but it was empty.\n",
+  );
+
+  let output = context
+    .new_command()
+    .args("info --quiet -")
+    .stdin("info/info_recursive_imports_test.ts\ninfo/info_type_import.ts\n")
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "error: Expected a single module specifier on stdin, but found multiple lines.\n",
+  );
+}
+
+#[test]
+fn info_json_node_builtin() {
+  let context = TestContextBuilder::new().build();
+  let output = context
+    .new_command()
+    .args("info --quiet --json info/node_builtin/main.ts")
+    .run();
+  output.assert_exit_code(0);
+
+  let json: serde_json::Value =
+    serde_json::from_str(output.combined_output()).unwrap();
+  let node_fs = json["modules"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|module| module["specifier"] == "node:fs")
+    .unwrap();
+  assert_eq!(node_fs["kind"], "node");
+  assert_eq!(node_fs["builtin"], true);
+}
+
+#[test]
+fn info_show_emit() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "import './mod.ts';\n");
+  temp_dir.write("mod.ts", "export const value: number = 1;\n");
+
+  context
+    .new_command()
+    .args("cache main.ts")
+    .run()
+    .skip_output_check();
+
+  let output = context
+    .new_command()
+    .args("info --quiet --show-emit main.ts")
+    .run();
+  output.assert_exit_code(0);
+  // both the root and its dependency have an emit in the cache
+  assert_eq!(output.combined_output().matches("(needs emit)").count(), 2);
+  assert_eq!(output.combined_output().matches("(emit: ").count(), 2);
+
+  let output = context.new_command().args("info --quiet main.ts").run();
+  output.assert_exit_code(0);
+  assert!(!output.combined_output().contains("(emit: "));
+}
+
+#[test]
+fn info_show_config() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", r#"{ "compilerOptions": { "strict": false } }"#);
+  temp_dir.write("main.ts", "export const value = 1;\n");
+
+  let output = context
+    .new_command()
+    .args("info --quiet --show-config main.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "[WILDCARD]
+config: file:///[WILDCARD]/deno.json
+compiler options:
+[WILDCARD]
+  strict: false
+[WILDCARD]",
+  );
+
+  let output = context
+    .new_command()
+    .args("info --quiet --json --show-config main.ts")
+    .run();
+  output.assert_exit_code(0);
+  let json: serde_json::Value =
+    serde_json::from_str(output.combined_output()).unwrap();
+  assert!(json["configFile"].as_str().unwrap().ends_with("/deno.json"));
+  assert_eq!(json["compilerOptions"]["strict"], false);
+}
+
+#[test]
+fn info_json_jsr_package() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .env("JSR_URL", "http://localhost:4545/jsr/registry/")
+    .build();
+  let output = context
+    .new_command()
+    .args("info --quiet --json info/jsr/main.ts")
+    .run();
+  output.assert_exit_code(0);
+  let json: serde_json::Value =
+    serde_json::from_str(output.combined_output()).unwrap();
+  let modules = json["modules"].as_array().unwrap();
+  let jsr_packages = modules
+    .iter()
+    .filter_map(|module| module["jsrPackage"].as_str())
+    .collect::<Vec<_>>();
+  assert_eq!(jsr_packages, vec!["@denotest/add@1.0.0"]);
+}
+
+#[test]
+fn info_diamond_deterministic() {
+  let context = TestContextBuilder::new().build();
+  let run_info = || {
+    let output = context
+      .new_command()
+      .args("info --quiet info/diamond/main.ts")
+      .run();
+    output.assert_exit_code(0);
+    output.assert_matches_file("info/diamond/main.out");
+    output.combined_output().to_string()
+  };
+
+  assert_eq!(run_info(), run_info());
+}
+
+#[test]
+fn info_show_cache() {
+  let context = TestContextBuilder::new().use_http_server().build();
+  let command = context
+    .new_command()
+    .args("info --quiet --show-cache http://localhost:4545/subdir/mod2.ts");
+
+  let output = command.run();
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "[WILDCARD]
+http://localhost:4545/subdir/mod2.ts ([WILDCARD]) (fetched)
+└── http://localhost:4545/subdir/print_hello.ts ([WILDCARD]) (fetched)
+",
+  );
+
+  let output = command.run();
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "[WILDCARD]
+http://localhost:4545/subdir/mod2.ts ([WILDCARD]) (cached)
+└── http://localhost:4545/subdir/print_hello.ts ([WILDCARD]) (cached)
+",
+  );
+}
+
 itest!(multiple_imports {
   args: "info http://127.0.0.1:4545/run/019_media_types.ts",
   output: "info/multiple_imports.out",
   http_server: true,
 });
 
+itest!(info_redirect {
+  args: "info http://localhost:4546/subdir/redirects/redirect2.js",
+  output: "info/info_redirect.out",
+  http_server: true,
+});
+
 itest!(info_ts_error {
   args: "info info/031_info_ts_error.ts",
   output: "info/031_info_ts_error.out",
@@ -61,6 +374,16 @@ itest!(info_json {
   output: "info/info_json.out",
 });
 
+itest!(info_json_compact {
+  args: "info --json --compact --unstable",
+  output: "info/info_json_compact.out",
+});
+
+itest!(info_json_sizes {
+  args: "info --json --sizes",
+  output: "info/info_json_sizes.out",
+});
+
 itest!(info_json_location {
   args: "info --json --unstable --location https://deno.land",
   output: "info/info_json_location.out",
@@ -78,12 +401,76 @@ itest!(json_file {
   exit_code: 0,
 });
 
+itest!(info_show_locations {
+  args: "info --quiet --show-locations info/json_output/main.ts",
+  output: "info/json_output/main_locations.out",
+  exit_code: 0,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_show_locations_json {
+  args: "info --quiet --json --show-locations info/json_output/main.ts",
+  output: "info/json_output/main_locations_json.out",
+  exit_code: 0,
+});
+
+itest!(info_jsonl {
+  args: "info --quiet --jsonl info/json_output/main.ts",
+  output: "info/json_output/main_jsonl.out",
+  exit_code: 0,
+});
+
 itest!(import_map_info {
   args:
     "info --quiet --import-map=import_maps/import_map.json import_maps/test.ts",
   output: "info/065_import_map_info.out",
 });
 
+itest!(info_json_cache_headers {
+  args: "info --quiet --json http://127.0.0.1:4545/etag_script.ts",
+  output: "info/info_json_cache_headers.out",
+  http_server: true,
+});
+
+itest!(info_show_checksums {
+  args: "info --quiet --show-checksums http://127.0.0.1:4545/etag_script.ts",
+  output: "info/info_show_checksums.out",
+  http_server: true,
+});
+
+itest!(info_json_checksums {
+  args:
+    "info --quiet --json --show-checksums http://127.0.0.1:4545/etag_script.ts",
+  output: "info/info_json_checksums.out",
+  http_server: true,
+});
+
+itest!(info_sbom {
+  args: "info --quiet --sbom http://127.0.0.1:4545/etag_script.ts",
+  output: "info/info_sbom.out",
+  http_server: true,
+});
+
+itest!(info_content_duplicates {
+  args: "info --quiet --duplicates info/content_duplicates/main.ts",
+  output: "info/content_duplicates/main.out",
+  http_server: true,
+});
+
+itest!(info_unique_content {
+  args: "info --quiet info/content_duplicates/main.ts",
+  output: "info/content_duplicates/main_info.out",
+  http_server: true,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_abbreviate {
+  args: "info --quiet --abbreviate info/abbreviate/main.ts",
+  output: "info/abbreviate/main.out",
+  http_server: true,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
 itest!(info_json_deps_order {
   args: "info --unstable --json info/076_info_json_deps_order.ts",
   output: "info/076_info_json_deps_order.out",
@@ -92,6 +479,13 @@ itest!(info_json_deps_order {
 itest!(info_missing_module {
   args: "info info/error_009_missing_js_module.js",
   output: "info/info_missing_module.out",
+  exit_code: 1,
+});
+
+itest!(info_missing_module_json {
+  args: "info --json info/error_009_missing_js_module.js",
+  output: "info/info_missing_module_json.out",
+  exit_code: 1,
 });
 
 itest!(info_lock {
@@ -102,6 +496,15 @@ itest!(info_lock {
   output: "lockfile/basic/fail.out",
 });
 
+itest!(info_lock_check {
+  args: "info --lock-check main.ts",
+  http_server: true,
+  cwd: Some("lockfile/basic"),
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 11,
+  output: "lockfile/basic/lock_check.out",
+});
+
 itest!(info_no_lock {
   args: "info --no-lock main.ts",
   http_server: true,
@@ -115,6 +518,233 @@ itest!(info_recursive_modules {
   exit_code: 0,
 });
 
+itest!(info_dot {
+  args: "info --quiet --dot info/info_recursive_imports_test.ts",
+  output: "info/info_dot.out",
+  exit_code: 0,
+});
+
+itest!(info_importers {
+  args: "info --quiet --importers info/recursive_imports/common.ts info/info_recursive_imports_test.ts",
+  output: "info/info_importers.out",
+  exit_code: 0,
+});
+
+itest!(info_path_to {
+  args: "info --quiet --path-to info/recursive_imports/C.ts info/info_recursive_imports_test.ts",
+  output: "info/info_path_to.out",
+  exit_code: 0,
+});
+
+itest!(info_path_to_not_found {
+  args: "info --quiet --path-to info/recursive_imports/A.ts info/recursive_imports/common.ts",
+  output_str: Some("[WILDCARD]/recursive_imports/A.ts was not found in graph.\n"),
+  exit_code: 1,
+});
+
+itest!(info_show_types {
+  args: "info --quiet --show-types info/info_recursive_imports_test.ts",
+  output: "info/info_show_types.out",
+  exit_code: 0,
+});
+
+itest!(info_compressed {
+  args: "info --quiet --compressed info/info_recursive_imports_test.ts",
+  output: "info/info_compressed.out",
+  exit_code: 0,
+});
+
+itest!(info_filter {
+  args: "info --quiet --filter */C.ts info/info_recursive_imports_test.ts",
+  output: "info/info_filter.out",
+  exit_code: 0,
+});
+
+itest!(info_exclude {
+  args: "info --quiet --exclude */common.ts --exclude-from-totals info/info_recursive_imports_test.ts",
+  output: "info/info_exclude.out",
+  exit_code: 0,
+});
+
+itest!(info_subtree_size {
+  args: "info --quiet --subtree-size info/info_recursive_imports_test.ts",
+  output: "info/info_subtree_size.out",
+  exit_code: 0,
+});
+
+itest!(info_flat {
+  args: "info --quiet --flat info/info_recursive_imports_test.ts",
+  output: "info/info_flat.out",
+  exit_code: 0,
+});
+
+itest!(info_csv {
+  args: "info --quiet --csv info/info_recursive_imports_test.ts",
+  output: "info/info_csv.out",
+  exit_code: 0,
+});
+
+itest!(info_max_nodes {
+  args: "info --quiet --max-nodes=3 info/info_recursive_imports_test.ts",
+  output: "info/info_max_nodes.out",
+  exit_code: 0,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_percent {
+  args: "info --quiet --percent info/info_recursive_imports_test.ts",
+  output: "info/info_percent.out",
+  exit_code: 0,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_percent_json {
+  args: "info --quiet --json --percent info/info_recursive_imports_test.ts",
+  output: "info/info_percent_json.out",
+  exit_code: 0,
+});
+
+itest!(info_top {
+  args: "info --quiet --top=3 info/info_recursive_imports_test.ts",
+  output: "info/info_top.out",
+  exit_code: 0,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_top_json {
+  args: "info --quiet --json --top=2 info/info_recursive_imports_test.ts",
+  output: "info/info_top_json.out",
+  exit_code: 0,
+});
+
+itest!(info_vendor_check {
+  args: "info --quiet --import-map info/vendor_check/vendor/import_map.json --vendor-check info/vendor_check/vendor info/vendor_check/main.ts",
+  output: "info/vendor_check/main.out",
+  exit_code: 1,
+  http_server: true,
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_flat_json {
+  args: "info --quiet --flat --json info/info_recursive_imports_test.ts",
+  output: "info/info_flat_json.out",
+  exit_code: 0,
+});
+
+itest!(info_flat_sort_size {
+  args: "info --quiet --flat --sort=size info/info_recursive_imports_test.ts",
+  output: "info/info_flat_sort_size.out",
+  exit_code: 0,
+});
+
+itest!(info_flat_format {
+  args: "info --quiet --flat --sort=size --format={size},{media_type},{dependency_count},{specifier} info/info_recursive_imports_test.ts",
+  output: "info/info_flat_format.out",
+  exit_code: 0,
+});
+
+itest!(info_dynamic_import {
+  args: "info --quiet info/dynamic_import/main.ts",
+  output: "info/dynamic_import/main.out",
+  exit_code: 0,
+});
+
+itest!(info_roots_only {
+  args: "info --quiet --roots-only info/info_recursive_imports_test.ts",
+  output: "info/info_roots_only.out",
+  exit_code: 0,
+});
+
+itest!(info_node_builtin {
+  args: "info --quiet info/node_builtin/main.ts",
+  output: "info/node_builtin/main.out",
+  exit_code: 0,
+});
+
+itest!(info_summary {
+  args: "info --quiet --summary info/info_recursive_imports_test.ts",
+  output: "info/info_summary.out",
+  exit_code: 0,
+});
+
+itest!(info_json_summary {
+  args: "info --quiet --json --summary info/info_recursive_imports_test.ts",
+  output: "info/info_json_summary.out",
+  exit_code: 0,
+});
+
+itest!(info_warn_size {
+  args: "info --quiet --warn-size=100b info/info_recursive_imports_test.ts",
+  output: "info/info_warn_size.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_fail_on_warn {
+  args: "info --quiet --warn-size=100b --fail-on-warn info/info_recursive_imports_test.ts",
+  output: "info/info_fail_on_warn.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 1,
+});
+
+itest!(info_diff {
+  args: "info --quiet --diff info/diff/b.ts info/diff/a.ts",
+  output: "info/diff/diff.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_diff_json {
+  args: "info --quiet --json --diff info/diff/b.ts info/diff/a.ts",
+  output: "info/diff/diff_json.out",
+});
+
+itest!(info_show_dep_counts {
+  args: "info --quiet --show-dep-counts info/info_recursive_imports_test.ts",
+  output: "info/info_show_dep_counts.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_ascii {
+  args: "info --quiet --ascii info/info_recursive_imports_test.ts",
+  output: "info/info_ascii.out",
+});
+
+itest!(info_jsr_package {
+  args: "info --quiet info/jsr/main.ts",
+  output: "info/jsr/main.out",
+  http_server: true,
+  envs: vec![
+    ("NO_COLOR".to_string(), "1".to_string()),
+    (
+      "JSR_URL".to_string(),
+      "http://localhost:4545/jsr/registry/".to_string()
+    ),
+  ],
+});
+
+itest!(info_by_host {
+  args: "info --quiet --by-host info/info_recursive_imports_test.ts",
+  output: "info/info_by_host.out",
+  exit_code: 0,
+});
+
+itest!(info_stats {
+  args: "info --quiet --stats info/info_recursive_imports_test.ts",
+  output: "info/info_stats.out",
+  exit_code: 0,
+});
+
+itest!(info_mermaid {
+  args: "info --quiet --mermaid info/info_recursive_imports_test.ts",
+  output: "info/info_mermaid.out",
+  exit_code: 0,
+});
+
+itest!(info_depth {
+  args: "info --quiet --depth=1 info/info_recursive_imports_test.ts",
+  output: "info/info_depth.out",
+  exit_code: 0,
+});
+
 itest!(info_type_import {
   args: "info info/info_type_import.ts",
   output: "info/info_type_import.out",
@@ -139,6 +769,178 @@ itest!(types_header_direct {
   http_server: true,
 });
 
+itest!(info_no_type_deps {
+  args: "info --reload --no-type-deps run/type_directives_01.ts",
+  output: "info/info_no_type_deps.out",
+  http_server: true,
+});
+
+itest!(info_kind_code_only {
+  args: "info --reload --kind=code-only run/type_directives_01.ts",
+  output: "info/info_kind_code_only.out",
+  http_server: true,
+});
+
+itest!(info_relative {
+  args: "info --quiet --relative info/info_recursive_imports_test.ts",
+  output: "info/info_relative.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_focus {
+  args: "info --quiet --focus=info/recursive_imports/A.ts info/info_recursive_imports_test.ts",
+  output: "info/info_focus.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_focus_not_found {
+  args: "info --quiet --focus=info/recursive_imports/D.ts info/info_recursive_imports_test.ts",
+  output_str: Some("error: file://[WILDCARD]/recursive_imports/D.ts was not found in graph.\n"),
+  exit_code: 1,
+});
+
+itest!(info_verbose_dedup_first_via {
+  args: "info --quiet --verbose-dedup info/info_recursive_imports_test.ts",
+  output: "info/info_first_via.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_count_only {
+  args: "info --quiet --count-only info/info_recursive_imports_test.ts",
+  output_str: Some("4\n"),
+});
+
+itest!(info_size_only {
+  args: "info --quiet --size-only info/info_recursive_imports_test.ts",
+  output_str: Some("451\n"),
+});
+
+itest!(info_fail_on_insecure {
+  args: "info --kind=code-only --fail-on-insecure run/type_directives_01.ts",
+  output: "info/info_fail_on_insecure.out",
+  http_server: true,
+  exit_code: 1,
+});
+
+itest!(info_resolve_only {
+  args: "info --quiet --resolve-only=../../subdir/mod1.ts info/json_output/main.ts",
+  output: "info/info_resolve_only.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_resolve_only_not_found {
+  args: "info --quiet --resolve-only=./missing.ts info/json_output/main.ts",
+  output_str: Some("error: ./missing.ts could not be resolved in the graph.\n"),
+  exit_code: 1,
+});
+
+itest!(info_treemap_json {
+  args: "info --quiet --treemap-json info/info_recursive_imports_test.ts",
+  output: "info/info_treemap_json.out",
+});
+
+itest!(info_split_sizes {
+  args: "info --quiet --split-sizes info/info_recursive_imports_test.ts",
+  output: "info/info_split_sizes.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_multiple_files {
+  args: "info --quiet info/info_type_import.ts info/info_recursive_imports_test.ts",
+  output: "info/info_multiple_files.out",
+});
+
+itest!(info_multiple_files_json {
+  args: "info --quiet --json --summary info/info_type_import.ts info/info_recursive_imports_test.ts",
+  output: "info/info_multiple_files_json.out",
+});
+
+itest!(info_multiple_files_flat_json {
+  args: "info --quiet --flat --json info/info_type_import.ts info/info_recursive_imports_test.ts",
+  output: "info/info_multiple_files_flat_json.out",
+});
+
+itest!(info_no_dedup {
+  args: "info --no-dedup info/info_recursive_imports_test.ts",
+  output: "info/info_no_dedup.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_show_import_map {
+  args: "info --quiet --show-import-map --import-map=import_maps/import_map.json import_maps/test.ts",
+  output: "info/info_show_import_map.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_show_import_map_json {
+  args: "info --quiet --json --show-import-map --import-map=import_maps/import_map.json import_maps/test.ts",
+  output: "info/info_show_import_map_json.out",
+});
+
+itest!(info_show_import_map_without_import_map {
+  args: "info --quiet --show-import-map info/info_recursive_imports_test.ts",
+  output_str: Some("error: --show-import-map requires an import map. Pass one with --import-map or add one to the deno.json.\n"),
+  exit_code: 1,
+});
+
+itest!(info_type_only_deps {
+  args: "info --reload --summary run/type_directives_01.ts",
+  output: "info/info_type_only_deps.out",
+  http_server: true,
+});
+
+itest!(info_entrypoint_from_config {
+  args: "info --quiet --entrypoint-from-config",
+  output: "info/entrypoint_from_config/main.out",
+  cwd: Some("info/entrypoint_from_config"),
+});
+
+itest!(info_width {
+  args: "info --quiet --width=40 info/info_recursive_imports_test.ts",
+  output: "info/info_width.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_runtime_only {
+  args: "info --reload --runtime-only run/type_directives_01.ts",
+  output: "info/info_runtime_only.out",
+  http_server: true,
+});
+
+itest!(info_legend {
+  args: "info --quiet --legend info/info_recursive_imports_test.ts",
+  output: "info/info_legend.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_legend_ascii {
+  args: "info --quiet --legend --ascii info/info_recursive_imports_test.ts",
+  output: "info/info_legend_ascii.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
+itest!(info_cache_only {
+  args: "info --quiet --cache-only info/cache_only/main.ts",
+  output: "info/info_cache_only.out",
+  exit_code: 1,
+});
+
+itest!(info_cache_only_json {
+  args: "info --quiet --cache-only --json info/cache_only/main.ts",
+  output: "info/info_cache_only_json.out",
+  exit_code: 1,
+});
+
+itest!(info_cache_only_local {
+  args: "info --quiet --cache-only info/cache_only/local.ts",
+  output: "info/info_cache_only_local.out",
+});
+
+itest!(info_dynamic_imports_summary {
+  args: "info --quiet --summary info/dynamic_import/main.ts",
+  output: "info/dynamic_import/main_summary.out",
+});
+
 itest!(with_config_override {
   args: "info info/with_config/test.ts --config info/with_config/deno-override.json --import-map info/with_config/import_map.json",
   output: "info/with_config/with_config.out",
@@ -920,6 +920,22 @@ itest!(info_chalk_display_node_modules_dir {
   temp_cwd: true,
 });
 
+itest!(info_chalk_display_no_npm {
+  args: "info --quiet --no-npm npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_no_npm.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_verbose_dedup {
+  args: "info --quiet --verbose-dedup npm/info_verbose_dedup/main.ts",
+  output: "npm/info_verbose_dedup/main_info.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
 itest!(info_chalk_json {
   args: "info --quiet --json npm/cjs_with_deps/main.js",
   output: "npm/cjs_with_deps/main_info_json.out",
@@ -928,6 +944,166 @@ itest!(info_chalk_json {
   http_server: true,
 });
 
+itest!(info_chalk_npm_urls {
+  args: "info --quiet --show-npm-urls npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_npm_urls.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_why {
+  args: "info --quiet --why npm:type-detect@4.0.8 npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_why.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_csv {
+  args: "info --quiet --csv npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_csv.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_npm_only {
+  args: "info --quiet --npm-only npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_npm_only.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_npm_only_json {
+  args: "info --quiet --npm-only --json npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_npm_only_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_cache_only {
+  args: "info --quiet --cache-only npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_cache_only.out",
+  exit_code: 1,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_dot {
+  args: "info --quiet --dot npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_dot.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_mermaid {
+  args: "info --quiet --mermaid npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_mermaid.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_deprecated {
+  args: "info --quiet npm/info_deprecated/main.ts",
+  output: "npm/info_deprecated/main_info.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_deprecated_json {
+  args: "info --quiet --json npm/info_deprecated/main.ts",
+  output: "npm/info_deprecated/main_info_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_install_scripts {
+  args: "info --quiet npm/info_install_scripts/main.ts",
+  output: "npm/info_install_scripts/main_info.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_install_scripts_json {
+  args: "info --quiet --json npm/info_install_scripts/main.ts",
+  output: "npm/info_install_scripts/main_info_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_peer_deps {
+  args: "info --quiet npm/info_peer_deps/main.ts",
+  output: "npm/info_peer_deps/main_info.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_peer_deps_json {
+  args: "info --quiet --json npm/info_peer_deps/main.ts",
+  output: "npm/info_peer_deps/main_info_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_npm_usage {
+  args: "info --quiet --npm-usage npm/info_npm_usage/main.ts",
+  output: "npm/info_npm_usage/main_info.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_npm_usage_json {
+  args: "info --quiet --npm-usage --json npm/info_npm_usage/main.ts",
+  output: "npm/info_npm_usage/main_info_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_import_map_conflict {
+  args: "info --quiet --import-map=npm/info_import_map_conflict/import_map.json npm/info_import_map_conflict/main.ts",
+  output: "npm/info_import_map_conflict/main_info.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_import_map_conflict_json {
+  args: "info --quiet --json --import-map=npm/info_import_map_conflict/import_map.json npm/info_import_map_conflict/main.ts",
+  output: "npm/info_import_map_conflict/main_info_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_group_npm_by_root {
+  args: "info --quiet --group-npm-by-root npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_group_npm_by_root.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(info_chalk_sbom {
+  args: "info --quiet --sbom npm/cjs_with_deps/main.js",
+  output: "npm/cjs_with_deps/main_info_sbom.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
 itest!(info_chalk_json_node_modules_dir {
   args:
     "info --quiet --node-modules-dir --json $TESTDATA/npm/cjs_with_deps/main.js",
@@ -954,6 +1130,14 @@ itest!(info_cli_chalk_json {
   http_server: true,
 });
 
+itest!(info_cli_side_effects_json {
+  args: "info --quiet --json npm:@denotest/side-effects@1.0.0",
+  output: "npm/info/side_effects_json.out",
+  exit_code: 0,
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
 #[test]
 fn lock_file_missing_top_level_package() {
   let _server = http_server();
@@ -578,6 +578,39 @@ async fn bundle_js_watch() {
   check_alive_then_kill(deno);
 }
 
+#[tokio::test]
+async fn info_watch() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.ts");
+  let mod_file = t.path().join("mod.ts");
+  file_to_watch.write("import './mod.ts';");
+  mod_file.write("export const a = 1;");
+  let mut deno = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("info")
+    .arg("--watch")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .stdout(std::process::Stdio::piped())
+    .stderr(std::process::Stdio::piped())
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut deno);
+
+  wait_contains("Info started", &mut stderr_lines).await;
+  wait_contains("dependencies: 1 unique", &mut stdout_lines).await;
+  wait_contains("Info finished", &mut stderr_lines).await;
+
+  // adding a dependency to a local module of the graph re-prints it
+  mod_file.write("import './other.ts';\nexport const a = 1;");
+  t.path().join("other.ts").write("export const b = 2;");
+  wait_contains("File change detected!", &mut stderr_lines).await;
+  wait_contains("dependencies: 2 unique", &mut stdout_lines).await;
+  wait_contains("Info finished", &mut stderr_lines).await;
+
+  check_alive_then_kill(deno);
+}
+
 /// Confirm that the watcher continues to work even if module resolution fails at the *first* attempt
 #[tokio::test]
 async fn bundle_watch_not_exit() {
@@ -0,0 +1,5 @@
+import { format } from "./format.ts";
+
+export function greet(name: string) {
+  console.log(format(name));
+}
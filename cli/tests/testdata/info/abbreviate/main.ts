@@ -0,0 +1,3 @@
+import { greet } from "http://localhost:4545/info/abbreviate/lib@1.0.0/mod.ts";
+
+greet("deno");
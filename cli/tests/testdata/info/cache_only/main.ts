@@ -0,0 +1,2 @@
+import "./local.ts";
+import "http://localhost:4545/subdir/print_hello.ts";
@@ -0,0 +1,2 @@
+import "http://127.0.0.1:4545/subdir/print_hello.ts";
+import "http://localhost:4545/subdir/print_hello.ts";
@@ -0,0 +1,5 @@
+import { shared } from "./shared.ts";
+
+export function left() {
+  return `left ${shared()}`;
+}
@@ -0,0 +1,4 @@
+import { left } from "./left.ts";
+import { right } from "./right.ts";
+
+console.log(left(), right());
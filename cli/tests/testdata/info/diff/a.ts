@@ -0,0 +1,2 @@
+import "./common.ts";
+import "./only_a.ts";
@@ -0,0 +1,3 @@
+import "./static.ts";
+
+await import("./dynamic.ts");
@@ -0,0 +1,3 @@
+import { add } from "http://localhost:4545/jsr/registry/@denotest/add/1.0.0/mod.ts";
+
+console.log(add(1, 2));
@@ -0,0 +1,5 @@
+import { printHello } from "http://localhost:4545/subdir/print_hello.ts";
+import { isMod4 } from "http://localhost:4545/subdir/mod4.js";
+
+printHello();
+console.log(isMod4);
@@ -0,0 +1,4 @@
+import mapped from "peer";
+import direct from "npm:@denotest/peer-dep-test-peer@2";
+
+console.log(mapped, direct);
@@ -0,0 +1,4 @@
+import version from "npm:@denotest/peer-dep-test-child@1";
+import { value } from "npm:@denotest/optional-peer-dep@1.0.0";
+
+console.log(version, value);
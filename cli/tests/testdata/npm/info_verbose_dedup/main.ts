@@ -0,0 +1,4 @@
+import chalk from "npm:chalk@4";
+import chalkMinor from "npm:chalk@4.1";
+
+console.log(chalk === chalkMinor);
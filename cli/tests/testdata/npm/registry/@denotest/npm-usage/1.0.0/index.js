@@ -0,0 +1,3 @@
+const { used } = require("./lib/used");
+
+module.exports.value = used();
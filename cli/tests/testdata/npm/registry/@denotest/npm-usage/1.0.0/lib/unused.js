@@ -0,0 +1,4 @@
+// only loaded by the tests of the package, so it's never imported
+module.exports.unused = () => {
+  throw new Error("not used");
+};
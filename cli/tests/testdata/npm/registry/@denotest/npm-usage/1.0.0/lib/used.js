@@ -0,0 +1 @@
+module.exports.used = () => 5;
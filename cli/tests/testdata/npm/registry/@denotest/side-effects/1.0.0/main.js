@@ -0,0 +1,3 @@
+require("./polyfill.js");
+
+module.exports.value = 5;
@@ -0,0 +1,318 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::ModuleSpecifier;
+use deno_graph::Module;
+use deno_graph::ModuleError;
+use deno_graph::ModuleGraph;
+use deno_graph::ModuleGraphError;
+use deno_runtime::colors;
+use walkdir::WalkDir;
+
+use crate::cache::CachedUrlMetadata;
+use crate::cache::HttpCache;
+use crate::display;
+use crate::errors::get_error_class_name;
+use crate::graph_util::LockfileDiff;
+use crate::npm::CliNpmResolver;
+use crate::util::checksum;
+
+use super::maybe_module_size;
+use super::ModuleChecksums;
+
+pub fn write_lockfile_diff<TWrite: Write>(
+  diff: &LockfileDiff,
+  lockfile_path: &Path,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  writeln!(
+    writer,
+    "{} {}",
+    colors::bold("lockfile:"),
+    lockfile_path.display()
+  )?;
+  if diff.is_empty() {
+    return writeln!(writer, "no differences found");
+  }
+  for (specifier, expected, actual) in &diff.mismatched {
+    writeln!(writer, "{} {}", colors::yellow("~"), specifier)?;
+    writeln!(writer, "  {}", colors::red(format!("- {expected}")))?;
+    writeln!(writer, "  {}", colors::green(format!("+ {actual}")))?;
+  }
+  for specifier in &diff.missing {
+    writeln!(
+      writer,
+      "{} {} {}",
+      colors::green("+"),
+      specifier,
+      colors::gray("(missing)")
+    )?;
+  }
+  writeln!(writer)?;
+  writeln!(
+    writer,
+    "{} mismatched, {} missing",
+    diff.mismatched.len(),
+    diff.missing.len()
+  )
+}
+
+/// Gets the modules that are loaded over `http:` instead of `https:`, which
+/// can be modified by anyone on the network path.
+pub fn find_insecure_imports(graph: &ModuleGraph) -> Vec<&ModuleSpecifier> {
+  graph
+    .modules()
+    .map(|module| module.specifier())
+    .filter(|specifier| specifier.scheme() == "http")
+    .collect()
+}
+
+/// Finds the modules whose source is larger than the threshold, with the
+/// largest first.
+pub fn find_large_modules(
+  graph: &ModuleGraph,
+  threshold: u64,
+) -> Vec<(&ModuleSpecifier, u64)> {
+  let mut large_modules = graph
+    .modules()
+    .filter_map(|module| {
+      let size = maybe_module_size(module)?;
+      (size > threshold).then(|| (module.specifier(), size))
+    })
+    .collect::<Vec<_>>();
+  large_modules.sort_by(|(a_specifier, a_size), (b_specifier, b_size)| {
+    b_size
+      .cmp(a_size)
+      .then_with(|| a_specifier.cmp(b_specifier))
+  });
+  large_modules
+}
+
+/// Finds the remote modules whose source isn't loaded from a file in the
+/// vendor directory. Vendored modules are mapped to the vendor directory by
+/// its import map, so they are local modules of the graph.
+pub fn find_unvendored_modules<'a>(
+  graph: &'a ModuleGraph,
+  vendor_dir: &Path,
+) -> Vec<&'a ModuleSpecifier> {
+  graph
+    .modules()
+    .filter(|module| matches!(module.specifier().scheme(), "http" | "https"))
+    .filter(|module| {
+      let maybe_local = match module {
+        Module::Esm(module) => module.maybe_cache_info.as_ref(),
+        Module::Json(module) => module.maybe_cache_info.as_ref(),
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+      }
+      .and_then(|cache_info| cache_info.local.as_ref());
+      !maybe_local.map_or(false, |local| local.starts_with(vendor_dir))
+    })
+    .map(|module| module.specifier())
+    .collect()
+}
+
+/// Finds the modules that failed to load because they aren't in the cache,
+/// which is how the file fetcher reports them with `--cached-only`, along
+/// with the npm packages whose folder isn't in the npm cache.
+pub fn find_uncached_modules<'a>(
+  graph: &'a ModuleGraph,
+  npm_resolver: &CliNpmResolver,
+) -> Vec<&'a ModuleSpecifier> {
+  let npm_snapshot = npm_resolver.snapshot();
+  let uncached_npm_modules = graph.modules().filter_map(|module| {
+    let Module::Npm(module) = module else {
+      return None;
+    };
+    let is_cached = npm_snapshot
+      .resolve_package_from_deno_module(&module.nv_reference.nv)
+      .ok()
+      .and_then(|package| {
+        npm_resolver
+          .resolve_pkg_folder_from_pkg_id(&package.id)
+          .ok()
+      })
+      .map_or(false, |folder| folder.exists());
+    if is_cached {
+      None
+    } else {
+      Some(&module.specifier)
+    }
+  });
+  let mut uncached = graph
+    .specifiers()
+    .filter_map(|(specifier, result)| match result {
+      Err(ModuleGraphError::ModuleError(ModuleError::LoadingErr(
+        _,
+        _,
+        err,
+      )))
+        if get_error_class_name(err.as_ref()) == "NotCached" =>
+      {
+        Some(specifier)
+      }
+      _ => None,
+    })
+    .chain(uncached_npm_modules)
+    .collect::<Vec<_>>();
+  uncached.sort();
+  uncached
+}
+
+pub fn write_uncached_modules<TWrite: Write>(
+  uncached: &[&ModuleSpecifier],
+  writer: &mut TWrite,
+) -> fmt::Result {
+  if uncached.is_empty() {
+    return writeln!(writer, "all modules are cached");
+  }
+  for specifier in uncached {
+    writeln!(writer, "{} {}", specifier, colors::red("(not cached)"))?;
+  }
+  Ok(())
+}
+
+pub fn write_unvendored_modules<TWrite: Write>(
+  unvendored: &[&ModuleSpecifier],
+  vendor_dir: &Path,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  writeln!(
+    writer,
+    "{} {}",
+    colors::bold("vendor directory:"),
+    vendor_dir.display()
+  )?;
+  if unvendored.is_empty() {
+    return writeln!(writer, "all remote modules are vendored");
+  }
+  for specifier in unvendored {
+    writeln!(writer, "{} {}", specifier, colors::red("(not vendored)"))?;
+  }
+  Ok(())
+}
+
+/// Remote modules that have the same source.
+pub struct ContentDuplicate<'a> {
+  pub checksum: String,
+  pub size: u64,
+  pub specifiers: Vec<&'a ModuleSpecifier>,
+}
+
+impl<'a> ContentDuplicate<'a> {
+  /// Bytes that would be saved by only fetching the source once.
+  pub fn wasted_size(&self) -> u64 {
+    self.size * (self.specifiers.len() as u64 - 1)
+  }
+}
+
+/// Finds the remote modules whose source is identical, which usually means
+/// the same file was fetched through different URLs (ex. a CDN alias).
+pub fn find_content_duplicates<'a>(
+  graph: &'a ModuleGraph,
+  checksums: &ModuleChecksums,
+) -> Vec<ContentDuplicate<'a>> {
+  let mut modules_by_checksum = BTreeMap::<String, Vec<&Module>>::new();
+  for module in graph.modules() {
+    if let Some(checksum) = checksums.remote(module) {
+      modules_by_checksum
+        .entry(checksum.to_string())
+        .or_default()
+        .push(module);
+    }
+  }
+  let mut duplicates = modules_by_checksum
+    .into_iter()
+    .filter(|(_, modules)| modules.len() > 1)
+    .map(|(checksum, modules)| {
+      let mut specifiers =
+        modules.iter().map(|m| m.specifier()).collect::<Vec<_>>();
+      specifiers.sort();
+      ContentDuplicate {
+        checksum,
+        size: maybe_module_size(modules[0]).unwrap_or(0),
+        specifiers,
+      }
+    })
+    .collect::<Vec<_>>();
+  duplicates.sort_by(|a, b| a.specifiers.cmp(&b.specifiers));
+  duplicates
+}
+
+pub fn write_content_duplicates<TWrite: Write>(
+  duplicates: &[ContentDuplicate],
+  writer: &mut TWrite,
+) -> fmt::Result {
+  if duplicates.is_empty() {
+    return writeln!(writer, "No remote modules with identical content found.");
+  }
+  for duplicate in duplicates {
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold(format!(
+        "{} modules with identical content",
+        duplicate.specifiers.len()
+      )),
+      colors::gray(format!(
+        "({} wasted)",
+        display::human_size(duplicate.wasted_size() as f64)
+      )),
+    )?;
+    for specifier in &duplicate.specifiers {
+      writeln!(writer, "  {specifier}")?;
+    }
+    writeln!(writer)?;
+  }
+  let total_wasted_size = duplicates
+    .iter()
+    .map(|duplicate| duplicate.wasted_size())
+    .sum::<u64>();
+  writeln!(
+    writer,
+    "{} {}",
+    colors::bold("wasted size:"),
+    display::human_size(total_wasted_size as f64)
+  )
+}
+
+/// Finds the files in the remote modules cache that aren't used by the
+/// graph along with their size, which includes their metadata file. The
+/// cache is shared, so these may still be used by other programs.
+pub fn find_orphaned_cache_files(
+  graph: &ModuleGraph,
+  http_cache: &HttpCache,
+) -> Vec<(PathBuf, u64)> {
+  // redirected urls have their own cache entry holding the redirect
+  let used_files = graph
+    .modules()
+    .map(|module| module.specifier())
+    .chain(graph.redirects.keys())
+    .filter_map(|specifier| http_cache.get_cache_filename(specifier))
+    .collect::<HashSet<_>>();
+  let mut orphans = WalkDir::new(&http_cache.location)
+    .into_iter()
+    .filter_map(|entry| entry.ok())
+    .filter(|entry| entry.file_type().is_file())
+    .map(|entry| entry.into_path())
+    .filter(|path| {
+      !path.to_string_lossy().ends_with(".metadata.json")
+        && !used_files.contains(path)
+    })
+    .map(|path| {
+      let size = [path.clone(), CachedUrlMetadata::filename(&path)]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+      (path, size)
+    })
+    .collect::<Vec<_>>();
+  orphans.sort();
+  orphans
+}
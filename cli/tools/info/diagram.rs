@@ -0,0 +1,256 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write;
+
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_graph::Resolution;
+
+use crate::args::InfoFlags;
+use crate::npm::CliNpmResolver;
+
+use super::maybe_size_to_plain_text;
+use super::GraphDisplayContext;
+
+struct DiagramNode {
+  key: String,
+  is_npm: bool,
+  size: Option<u64>,
+}
+
+struct DiagramEdge {
+  from: String,
+  to: String,
+  type_dep: bool,
+}
+
+/// Assigns Mermaid node ids, which may only contain alphanumeric characters
+/// and underscores, to the specifiers in the graph.
+#[derive(Default)]
+struct MermaidIds {
+  ids: HashMap<String, String>,
+  used: HashSet<String>,
+}
+
+impl MermaidIds {
+  pub fn get(&mut self, key: &str) -> String {
+    if let Some(id) = self.ids.get(key) {
+      return id.clone();
+    }
+    let sanitized = key
+      .chars()
+      .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+      .collect::<String>();
+    let mut id = sanitized.clone();
+    let mut suffix = 1;
+    while !self.used.insert(id.clone()) {
+      suffix += 1;
+      id = format!("{sanitized}_{suffix}");
+    }
+    self.ids.insert(key.to_string(), id.clone());
+    id
+  }
+}
+
+fn dot_quote(text: &str) -> String {
+  format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Shortens long specifiers to their trailing portion and escapes the
+/// characters that would end a quoted Mermaid label.
+fn mermaid_label(key: &str) -> String {
+  const MAX_LABEL_LEN: usize = 60;
+  let char_count = key.chars().count();
+  let label = if char_count > MAX_LABEL_LEN {
+    let tail = key
+      .chars()
+      .skip(char_count - (MAX_LABEL_LEN - 1))
+      .collect::<String>();
+    format!("…{tail}")
+  } else {
+    key.to_string()
+  };
+  label.replace('"', "#quot;")
+}
+
+impl<'a> GraphDisplayContext<'a> {
+  /// Writes the module graph in the Graphviz DOT format.
+  pub fn write_dot<TWrite: Write>(
+    graph: &'a ModuleGraph,
+    npm_resolver: &'a CliNpmResolver,
+    info_flags: &InfoFlags,
+    writer: &mut TWrite,
+  ) -> fmt::Result {
+    Self::new(graph, npm_resolver, info_flags).into_dot_writer(writer)
+  }
+
+  /// Writes the module graph as a Mermaid flowchart.
+  pub fn write_mermaid<TWrite: Write>(
+    graph: &'a ModuleGraph,
+    npm_resolver: &'a CliNpmResolver,
+    info_flags: &InfoFlags,
+    writer: &mut TWrite,
+  ) -> fmt::Result {
+    Self::new(graph, npm_resolver, info_flags).into_mermaid_writer(writer)
+  }
+
+  fn into_dot_writer<TWrite: Write>(
+    mut self,
+    writer: &mut TWrite,
+  ) -> fmt::Result {
+    let (nodes, edges) = self.build_diagram();
+    writeln!(writer, "digraph {{")?;
+    for node in &nodes {
+      if node.is_npm {
+        writeln!(writer, "  {} [shape=box];", dot_quote(&node.key))?;
+      } else {
+        writeln!(writer, "  {};", dot_quote(&node.key))?;
+      }
+    }
+    for edge in &edges {
+      writeln!(
+        writer,
+        "  {} -> {}{};",
+        dot_quote(&edge.from),
+        dot_quote(&edge.to),
+        if edge.type_dep { " [style=dashed]" } else { "" }
+      )?;
+    }
+    writeln!(writer, "}}")
+  }
+
+  fn into_mermaid_writer<TWrite: Write>(
+    mut self,
+    writer: &mut TWrite,
+  ) -> fmt::Result {
+    let (nodes, edges) = self.build_diagram();
+    let mut ids = MermaidIds::default();
+    writeln!(writer, "flowchart TD")?;
+    for node in &nodes {
+      writeln!(
+        writer,
+        "  {}[\"{} {}\"]",
+        ids.get(&node.key),
+        mermaid_label(&node.key),
+        maybe_size_to_plain_text(node.size),
+      )?;
+    }
+    for edge in &edges {
+      writeln!(
+        writer,
+        "  {} {} {}",
+        ids.get(&edge.from),
+        if edge.type_dep { "-.->" } else { "-->" },
+        ids.get(&edge.to),
+      )?;
+    }
+    Ok(())
+  }
+
+  /// Collects the nodes and edges of the module graph for the diagram
+  /// outputs, emitting each module and npm package once.
+  fn build_diagram(&mut self) -> (Vec<DiagramNode>, Vec<DiagramEdge>) {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    for module in self.graph.modules() {
+      let key = self.module_key(module);
+      if self.seen.insert(key.clone(), None).is_some() {
+        continue;
+      }
+      if let Some(package) = module
+        .npm()
+        .and_then(|npm| self.npm_info.resolve_package(&npm.nv_reference.nv))
+      {
+        nodes.push(DiagramNode {
+          key,
+          is_npm: true,
+          size: self.npm_info.package_sizes.get(&package.id).copied(),
+        });
+        continue;
+      }
+      let size = match module {
+        Module::Esm(module) => Some(module.size() as u64),
+        Module::Json(module) => Some(module.size() as u64),
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+      };
+      if let Some(module) = module.esm() {
+        if let Some(types_dep) = &module.maybe_types_dependency {
+          edges.extend(self.build_diagram_edge(
+            &key,
+            &types_dep.dependency,
+            true,
+          ));
+        }
+        for dep in module.dependencies.values() {
+          edges.extend(self.build_diagram_edge(&key, &dep.maybe_code, false));
+          edges.extend(self.build_diagram_edge(&key, &dep.maybe_type, true));
+        }
+      }
+      nodes.push(DiagramNode {
+        key,
+        is_npm: module.npm().is_some(),
+        size,
+      });
+    }
+
+    let mut packages = self.npm_info.packages.values().collect::<Vec<_>>();
+    packages.sort_by(|a, b| a.id.cmp(&b.id));
+    for package in packages {
+      let key = format!("npm:{}", package.id.as_serialized());
+      let mut deps = package.dependencies.values().collect::<Vec<_>>();
+      deps.sort();
+      for dep_id in deps {
+        edges.push(DiagramEdge {
+          from: key.clone(),
+          to: format!("npm:{}", dep_id.as_serialized()),
+          type_dep: false,
+        });
+      }
+      // the packages that the graph imports directly already have a node
+      if self.seen.insert(key.clone(), None).is_none() {
+        nodes.push(DiagramNode {
+          key,
+          is_npm: true,
+          size: self.npm_info.package_sizes.get(&package.id).copied(),
+        });
+      }
+    }
+    (nodes, edges)
+  }
+
+  fn build_diagram_edge(
+    &self,
+    from: &str,
+    resolution: &Resolution,
+    type_dep: bool,
+  ) -> Option<DiagramEdge> {
+    let Resolution::Ok(resolved) = resolution else {
+      return None;
+    };
+    let specifier = self.graph.resolve(&resolved.specifier);
+    let to = match self.graph.try_get(&specifier) {
+      Ok(Some(module)) => self.module_key(module),
+      _ => specifier.to_string(),
+    };
+    Some(DiagramEdge {
+      from: from.to_string(),
+      to,
+      type_dep,
+    })
+  }
+
+  /// Gets the key that identifies a module's node in the graph, where npm
+  /// modules are identified by the package they resolve to.
+  fn module_key(&self, module: &Module) -> String {
+    match module
+      .npm()
+      .and_then(|npm| self.npm_info.resolve_package(&npm.nv_reference.nv))
+    {
+      Some(package) => format!("npm:{}", package.id.as_serialized()),
+      None => module.specifier().to_string(),
+    }
+  }
+}
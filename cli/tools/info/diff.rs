@@ -0,0 +1,174 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_npm::resolution::NpmResolutionSnapshot;
+use deno_runtime::colors;
+
+use crate::args::InfoFlags;
+use crate::display;
+use crate::npm::CliNpmResolver;
+
+use super::maybe_module_size;
+use super::maybe_size_to_text;
+use super::write_json;
+use super::write_output;
+use super::NpmInfo;
+
+/// Writes the modules and npm packages that were added, removed or changed
+/// in size in `other_graph` compared to `graph`.
+pub fn write_graph_diff(
+  graph: &ModuleGraph,
+  other_graph: &ModuleGraph,
+  npm_resolver: &CliNpmResolver,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  // both graphs are resolved in the same npm snapshot
+  let npm_snapshot = npm_resolver.snapshot();
+  let diff = diff_graphs(
+    &graph_entry_sizes(graph, npm_resolver, &npm_snapshot),
+    &graph_entry_sizes(other_graph, npm_resolver, &npm_snapshot),
+  );
+  if info_flags.json {
+    write_json(&diff.to_json(), info_flags)?;
+  } else {
+    let mut output = String::new();
+    write_diff_text(&diff, &mut output)?;
+    write_output(output.as_bytes(), info_flags)?;
+  }
+  Ok(())
+}
+
+/// Gets the size of each module and npm package in the graph, keyed by the
+/// specifier, which can be compared against another graph.
+fn graph_entry_sizes(
+  graph: &ModuleGraph,
+  npm_resolver: &CliNpmResolver,
+  npm_snapshot: &NpmResolutionSnapshot,
+) -> BTreeMap<String, Option<u64>> {
+  let mut entries = BTreeMap::new();
+  for module in graph.modules() {
+    if let Module::Esm(_) | Module::Json(_) = module {
+      entries.insert(module.specifier().to_string(), maybe_module_size(module));
+    }
+  }
+  let npm_info = NpmInfo::build(graph, npm_resolver, npm_snapshot);
+  for id in npm_info.packages.keys() {
+    entries.insert(
+      format!("npm:{}", id.as_serialized()),
+      npm_info.package_sizes.get(id).copied(),
+    );
+  }
+  entries
+}
+
+/// The differences between the modules of two graphs.
+#[derive(Debug, Default)]
+struct GraphDiff {
+  added: Vec<(String, Option<u64>)>,
+  removed: Vec<(String, Option<u64>)>,
+  /// Entries in both graphs with a different size, along with the old and
+  /// new sizes.
+  changed: Vec<(String, u64, u64)>,
+}
+
+impl GraphDiff {
+  fn to_json(&self) -> serde_json::Value {
+    let to_json = |entries: &[(String, Option<u64>)]| {
+      entries
+        .iter()
+        .map(
+          |(specifier, size)| json!({ "specifier": specifier, "size": size }),
+        )
+        .collect::<Vec<_>>()
+    };
+    let changed = self
+      .changed
+      .iter()
+      .map(|(specifier, old_size, new_size)| {
+        json!({
+          "specifier": specifier,
+          "oldSize": old_size,
+          "newSize": new_size,
+        })
+      })
+      .collect::<Vec<_>>();
+    json!({
+      "added": to_json(&self.added),
+      "removed": to_json(&self.removed),
+      "changed": changed,
+    })
+  }
+}
+
+fn diff_graphs(
+  old: &BTreeMap<String, Option<u64>>,
+  new: &BTreeMap<String, Option<u64>>,
+) -> GraphDiff {
+  let mut diff = GraphDiff::default();
+  for (specifier, new_size) in new {
+    match old.get(specifier) {
+      None => diff.added.push((specifier.clone(), *new_size)),
+      Some(old_size) => {
+        if let (Some(old_size), Some(new_size)) = (old_size, new_size) {
+          if old_size != new_size {
+            diff.changed.push((specifier.clone(), *old_size, *new_size));
+          }
+        }
+      }
+    }
+  }
+  for (specifier, old_size) in old {
+    if !new.contains_key(specifier) {
+      diff.removed.push((specifier.clone(), *old_size));
+    }
+  }
+  diff
+}
+
+fn write_diff_text<TWrite: Write>(
+  diff: &GraphDiff,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  for (specifier, size) in &diff.added {
+    let text = format!("+ {} {}", specifier, maybe_size_to_text(*size));
+    writeln!(writer, "{}", colors::green(text))?;
+  }
+  for (specifier, size) in &diff.removed {
+    let text = format!("- {} {}", specifier, maybe_size_to_text(*size));
+    writeln!(writer, "{}", colors::red(text))?;
+  }
+  for (specifier, old_size, new_size) in &diff.changed {
+    let delta = *new_size as f64 - *old_size as f64;
+    writeln!(
+      writer,
+      "{} {} ({} -> {}, {}{})",
+      colors::yellow("~"),
+      specifier,
+      display::human_size(*old_size as f64),
+      display::human_size(*new_size as f64),
+      if delta > 0.0 { "+" } else { "" },
+      display::human_size(delta)
+    )?;
+  }
+  if !diff.added.is_empty()
+    || !diff.removed.is_empty()
+    || !diff.changed.is_empty()
+  {
+    writeln!(writer)?;
+  }
+  writeln!(
+    writer,
+    "{} added, {} removed, {} changed",
+    diff.added.len(),
+    diff.removed.len(),
+    diff.changed.len()
+  )
+}
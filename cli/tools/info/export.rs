@@ -0,0 +1,217 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Write;
+
+use deno_ast::MediaType;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+
+use crate::args::InfoFormatToken;
+use crate::args::InfoSortOrder;
+use crate::util::checksum;
+
+use super::maybe_module_size;
+use super::maybe_size_to_text;
+use super::ModuleChecksums;
+use super::NpmInfo;
+
+/// Builds a minimal CycloneDX software bill of materials with a component
+/// for each npm package and remote module, sorted by name.
+pub fn build_sbom(
+  graph: &ModuleGraph,
+  npm_info: &NpmInfo,
+  checksums: &ModuleChecksums,
+) -> serde_json::Value {
+  // packages copied for peer dependencies share the same name and version
+  let npm_packages = npm_info
+    .packages
+    .keys()
+    .map(|id| &id.nv)
+    .collect::<BTreeSet<_>>();
+  let mut components = npm_packages
+    .into_iter()
+    .map(|nv| {
+      let name = nv.name.to_string();
+      let version = nv.version.to_string();
+      let purl = format!("pkg:npm/{}@{}", name.replace('@', "%40"), version);
+      let component = json!({
+        "type": "library",
+        "name": name,
+        "version": version,
+        "purl": purl,
+      });
+      (name, component)
+    })
+    .collect::<Vec<_>>();
+  components.extend(graph.modules().filter_map(|module| {
+    let checksum = checksums.remote(module)?;
+    let name = module.specifier().to_string();
+    let component = json!({
+      "type": "file",
+      "name": name,
+      "hashes": [{ "alg": "SHA-256", "content": checksum }],
+    });
+    Some((name, component))
+  }));
+  components.sort_by(|(a, _), (b, _)| a.cmp(b));
+  json!({
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.4",
+    "version": 1,
+    "components": components
+      .into_iter()
+      .map(|(_, component)| component)
+      .collect::<Vec<_>>(),
+  })
+}
+
+/// Writes each unique module of the graph on its own line, which is easier
+/// to diff between two runs than the tree.
+pub fn write_flat_list<TWrite: Write>(
+  graph: &ModuleGraph,
+  sort: InfoSortOrder,
+  maybe_format: Option<&[InfoFormatToken]>,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  let mut modules = graph
+    .modules()
+    .map(|module| (module, maybe_module_size(module)))
+    .collect::<Vec<_>>();
+  modules.sort_by(|(a, _), (b, _)| a.specifier().cmp(b.specifier()));
+  if sort == InfoSortOrder::Size {
+    // stable, so modules of the same size stay sorted by specifier
+    modules.sort_by(|(_, a), (_, b)| b.cmp(a));
+  }
+  for (module, maybe_size) in modules {
+    match maybe_format {
+      Some(format) => write_formatted_module(module, format, writer)?,
+      None => writeln!(
+        writer,
+        "{} {}",
+        module.specifier(),
+        maybe_size_to_text(maybe_size)
+      )?,
+    }
+  }
+  Ok(())
+}
+
+/// Writes a line for the module with the placeholders of the `--format`
+/// template replaced. Fields that don't apply to the module are left empty.
+fn write_formatted_module<TWrite: Write>(
+  module: &Module,
+  format: &[InfoFormatToken],
+  writer: &mut TWrite,
+) -> fmt::Result {
+  for token in format {
+    match token {
+      InfoFormatToken::Text(text) => write!(writer, "{text}")?,
+      InfoFormatToken::Specifier => write!(writer, "{}", module.specifier())?,
+      InfoFormatToken::Size => {
+        if let Some(size) = maybe_module_size(module) {
+          write!(writer, "{size}")?;
+        }
+      }
+      InfoFormatToken::MediaType => match module {
+        Module::Esm(module) => write!(writer, "{}", module.media_type)?,
+        Module::Json(module) => write!(writer, "{}", module.media_type)?,
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => {}
+      },
+      InfoFormatToken::DependencyCount => write!(
+        writer,
+        "{}",
+        module.esm().map_or(0, |module| module.dependencies.len())
+      )?,
+    }
+  }
+  writeln!(writer)
+}
+
+/// Writes a row for each module and npm package with the same kinds as the
+/// json output. npm packages are identified by their package id.
+pub fn write_csv<TWrite: Write>(
+  graph: &ModuleGraph,
+  npm_info: &NpmInfo,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  write_csv_row(
+    writer,
+    &[
+      "specifier",
+      "kind",
+      "media_type",
+      "size",
+      "dependency_count",
+    ],
+  )?;
+  for module in graph.modules() {
+    let (kind, media_type, dependency_count) = match module {
+      Module::Esm(module) => (
+        "esm",
+        module.media_type.to_string(),
+        module.dependencies.len(),
+      ),
+      Module::Json(module) => ("asserted", module.media_type.to_string(), 0),
+      // shown below as the package they resolve to
+      Module::Npm(_) => continue,
+      Module::Node(_) => ("node", String::new(), 0),
+      Module::External(_) => ("external", String::new(), 0),
+    };
+    let size = maybe_module_size(module)
+      .map(|size| size.to_string())
+      .unwrap_or_default();
+    write_csv_row(
+      writer,
+      &[
+        module.specifier().as_str(),
+        kind,
+        &media_type,
+        &size,
+        &dependency_count.to_string(),
+      ],
+    )?;
+  }
+  let mut packages = npm_info.packages.values().collect::<Vec<_>>();
+  packages.sort_by_cached_key(|package| package.id.as_serialized());
+  for package in packages {
+    let size = npm_info
+      .package_sizes
+      .get(&package.id)
+      .map(|size| size.to_string())
+      .unwrap_or_default();
+    write_csv_row(
+      writer,
+      &[
+        &package.id.as_serialized(),
+        "npm",
+        "",
+        &size,
+        &package.dependencies.len().to_string(),
+      ],
+    )?;
+  }
+  Ok(())
+}
+
+/// Writes the fields separated by commas, quoting the ones that contain a
+/// comma, quote or line break and doubling the quotes inside them.
+fn write_csv_row<TWrite: Write>(
+  writer: &mut TWrite,
+  fields: &[&str],
+) -> fmt::Result {
+  for (i, field) in fields.iter().enumerate() {
+    if i > 0 {
+      write!(writer, ",")?;
+    }
+    if field.contains([',', '"', '\n', '\r']) {
+      write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+    } else {
+      write!(writer, "{field}")?;
+    }
+  }
+  writeln!(writer)
+}
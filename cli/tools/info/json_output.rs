@@ -0,0 +1,301 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::resolve_import;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::ModuleGraph;
+use deno_semver::Version;
+use import_map::ImportMap;
+
+use crate::args::jsr_url;
+use crate::file_fetcher::FileFetcher;
+use crate::util::checksum;
+
+use super::cache_state_text;
+use super::ModuleChecksums;
+
+/// Adds the response headers stored in the HTTP cache to each remote module,
+/// which is useful for auditing how the modules were served, along with
+/// whether the module was downloaded when `include_cache_state` is set.
+pub fn add_cache_info_to_json(
+  json: &mut serde_json::Value,
+  file_fetcher: &FileFetcher,
+  include_cache_state: bool,
+) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut() {
+    let Some(module) = module.as_object_mut() else {
+      continue;
+    };
+    if module.get("kind").and_then(|k| k.as_str()) != Some("esm") {
+      continue;
+    }
+    let maybe_specifier = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|s| ModuleSpecifier::parse(s).ok())
+      .filter(|s| matches!(s.scheme(), "http" | "https"));
+    let Some(specifier) = maybe_specifier else {
+      continue;
+    };
+    if include_cache_state {
+      module.insert(
+        "cacheState".to_string(),
+        cache_state_text(file_fetcher, &specifier).into(),
+      );
+    }
+    if let Ok((_, headers, _)) = file_fetcher.http_cache.get(&specifier) {
+      let headers = headers
+        .into_iter()
+        .map(|(name, value)| (name, serde_json::Value::String(value)))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .collect::<serde_json::Map<_, _>>();
+      module.insert("headers".to_string(), headers.into());
+    }
+  }
+}
+
+pub fn add_checksums_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+  checksums: &ModuleChecksums,
+) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut() {
+    let maybe_checksum = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|s| ModuleSpecifier::parse(s).ok())
+      .and_then(|s| graph.get(&s))
+      .and_then(|module| checksums.remote(module));
+    if let Some(checksum) = maybe_checksum {
+      module["checksum"] = checksum.into();
+    }
+  }
+}
+
+/// Gets the `@scope/name@version` of the JSR package that a module was
+/// downloaded from. The registry serves the files of each package version at
+/// `<registry>/@scope/name/version/<path>`.
+pub fn jsr_package_from_specifier(
+  specifier: &ModuleSpecifier,
+) -> Option<String> {
+  let path = specifier.as_str().strip_prefix(jsr_url().as_str())?;
+  let mut parts = path.splitn(4, '/');
+  let scope = parts.next()?;
+  let name = parts.next()?;
+  let version = parts.next()?;
+  let file_path = parts.next()?;
+  if scope.len() < 2 || !scope.starts_with('@') || name.is_empty() {
+    return None;
+  }
+  if file_path.is_empty() || Version::parse_from_npm(version).is_err() {
+    return None;
+  }
+  Some(format!("{scope}/{name}@{version}"))
+}
+
+pub fn add_jsr_packages_to_json(json: &mut serde_json::Value) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut() {
+    let maybe_package = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|s| ModuleSpecifier::parse(s).ok())
+      .and_then(|specifier| jsr_package_from_specifier(&specifier));
+    if let Some(package) = maybe_package {
+      module["jsrPackage"] = package.into();
+    }
+  }
+}
+
+/// Sorts the modules and their dependencies by specifier so the output
+/// doesn't depend on the order the graph was built in.
+pub fn sort_json_modules(json: &mut serde_json::Value) {
+  fn specifier_of(value: &serde_json::Value) -> &str {
+    value
+      .get("specifier")
+      .and_then(|specifier| specifier.as_str())
+      .unwrap_or_default()
+  }
+
+  let Some(modules) = json.get_mut("modules").and_then(|m| m.as_array_mut())
+  else {
+    return;
+  };
+  modules.sort_by(|a, b| specifier_of(a).cmp(specifier_of(b)));
+  for module in modules {
+    if let Some(deps) = module
+      .get_mut("dependencies")
+      .and_then(|deps| deps.as_array_mut())
+    {
+      deps.sort_by(|a, b| specifier_of(a).cmp(specifier_of(b)));
+    }
+  }
+}
+
+pub fn mark_node_builtins_in_json(json: &mut serde_json::Value) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut() {
+    let is_node_builtin = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .map_or(false, |s| s.starts_with("node:"));
+    if is_node_builtin {
+      module["kind"] = "node".into();
+      module["builtin"] = true.into();
+    }
+  }
+}
+
+/// Marks the dependencies that are dynamically imported, which are usually
+/// code-split boundaries, and the ones that were remapped by the import map.
+pub fn add_dependency_flags_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+  maybe_import_map: Option<&ImportMap>,
+) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut() {
+    let maybe_module = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|s| ModuleSpecifier::parse(s).ok())
+      .and_then(|s| graph.get(&s))
+      .and_then(|m| m.esm());
+    let Some(graph_module) = maybe_module else {
+      continue;
+    };
+    let dependencies = module
+      .get_mut("dependencies")
+      .and_then(|d| d.as_array_mut());
+    for dependency in dependencies.into_iter().flatten() {
+      let Some((specifier, dep)) = dependency
+        .get("specifier")
+        .and_then(|s| s.as_str())
+        .and_then(|s| graph_module.dependencies.get_key_value(s))
+      else {
+        continue;
+      };
+      if dep.is_dynamic {
+        dependency["dynamic"] = true.into();
+      }
+      if is_mapped_dependency(
+        maybe_import_map,
+        &graph_module.specifier,
+        specifier,
+      ) {
+        dependency["mapped"] = true.into();
+      }
+    }
+  }
+}
+
+/// Adds the size of each module as a percentage of the total size of the
+/// modules and npm packages.
+pub fn add_size_percentages_to_json(
+  json: &mut serde_json::Value,
+  total_size: u64,
+) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  for module in modules.into_iter().flatten() {
+    let Some(size) = module.get("size").and_then(|s| s.as_u64()) else {
+      continue;
+    };
+    // rounded to two decimals
+    let percent =
+      (size_percentage(size as f64, total_size as f64) * 100.0).round() / 100.0;
+    module["percentOfTotal"] = percent.into();
+  }
+}
+
+pub fn size_percentage(size: f64, total_size: f64) -> f64 {
+  if total_size > 0.0 {
+    size / total_size * 100.0
+  } else {
+    0.0
+  }
+}
+
+/// Adds the range of the import statement of each dependency, preferring the
+/// code import when the dependency also has a type import.
+pub fn add_dependency_ranges_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut() {
+    let maybe_module = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|s| ModuleSpecifier::parse(s).ok())
+      .and_then(|s| graph.get(&s))
+      .and_then(|m| m.esm());
+    let Some(graph_module) = maybe_module else {
+      continue;
+    };
+    let dependencies = module
+      .get_mut("dependencies")
+      .and_then(|d| d.as_array_mut());
+    for dependency in dependencies.into_iter().flatten() {
+      let maybe_range = dependency
+        .get("specifier")
+        .and_then(|s| s.as_str())
+        .and_then(|s| graph_module.dependencies.get(s))
+        .and_then(|dep| dep.maybe_code.ok().or_else(|| dep.maybe_type.ok()))
+        .map(|resolved| &resolved.range);
+      if let Some(range) = maybe_range {
+        dependency["range"] = json!({
+          "start": {
+            "line": range.start.line,
+            "character": range.start.character,
+          },
+          "end": {
+            "line": range.end.line,
+            "character": range.end.character,
+          },
+        });
+      }
+    }
+  }
+}
+
+/// Gets if the dependency was remapped by the import map instead of
+/// resolving to its specifier relative to the importing module. Bare
+/// specifiers that the import map doesn't have an entry for are resolved
+/// by other means (ex. the package.json), so they aren't mapped.
+pub fn is_mapped_dependency(
+  maybe_import_map: Option<&ImportMap>,
+  referrer: &ModuleSpecifier,
+  specifier: &str,
+) -> bool {
+  let Some(import_map) = maybe_import_map else {
+    return false;
+  };
+  let Ok(mapped) = import_map.resolve(specifier, referrer) else {
+    return false;
+  };
+  resolve_import(specifier, referrer.as_str()).ok().as_ref() != Some(&mapped)
+}
@@ -0,0 +1,3634 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write as _;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::resolve_import;
+use deno_core::resolve_url_or_path;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::Dependency;
+use deno_graph::EsmModule;
+use deno_graph::Module;
+use deno_graph::ModuleError;
+use deno_graph::ModuleGraph;
+use deno_graph::ModuleGraphError;
+use deno_graph::Resolution;
+use deno_npm::NpmPackageId;
+use deno_npm::NpmResolutionPackage;
+use deno_runtime::colors;
+use deno_runtime::deno_node::NodeModuleKind;
+use deno_semver::Version;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use import_map::ImportMap;
+use import_map::SpecifierMap;
+
+use crate::args::CliOptions;
+use crate::args::Flags;
+use crate::args::InfoColorMode;
+use crate::args::InfoFlags;
+use crate::args::InfoSortOrder;
+use crate::args::TsConfig;
+use crate::args::TsConfigType;
+use crate::display;
+use crate::factory::CliFactory;
+use crate::factory::CliFactoryBuilder;
+use crate::file_fetcher::FileFetcher;
+use crate::graph_util::graph_lock_diff;
+use crate::graph_util::graph_lock_or_exit;
+use crate::npm::CliNpmResolver;
+use crate::util::checksum;
+use crate::util::file_watcher;
+use crate::util::fs::dir_size;
+use crate::util::glob::GlobPattern;
+use crate::util::glob::GlobSet;
+use crate::util::path::relative_specifier;
+
+mod checks;
+mod diagram;
+mod diff;
+mod export;
+mod json_output;
+mod npm;
+mod paths;
+mod stats;
+mod tree;
+mod treemap;
+
+use checks::find_content_duplicates;
+use checks::find_insecure_imports;
+use checks::find_large_modules;
+use checks::find_orphaned_cache_files;
+use checks::find_uncached_modules;
+use checks::find_unvendored_modules;
+use checks::write_content_duplicates;
+use checks::write_lockfile_diff;
+use checks::write_uncached_modules;
+use checks::write_unvendored_modules;
+use export::build_sbom;
+use export::write_csv;
+use export::write_flat_list;
+use json_output::add_cache_info_to_json;
+use json_output::add_checksums_to_json;
+use json_output::add_dependency_flags_to_json;
+use json_output::add_dependency_ranges_to_json;
+use json_output::add_jsr_packages_to_json;
+use json_output::add_size_percentages_to_json;
+use json_output::is_mapped_dependency;
+use json_output::jsr_package_from_specifier;
+use json_output::mark_node_builtins_in_json;
+use json_output::size_percentage;
+use json_output::sort_json_modules;
+use npm::add_npm_packages_to_json;
+use npm::find_duplicate_npm_packages;
+use npm::find_npm_package_usages;
+use npm::npm_package_tarball;
+use npm::write_npm_package_usages;
+use npm::NpmInfo;
+use stats::add_subtree_sizes_to_json;
+use stats::count_dynamic_imports;
+use stats::count_local_and_remote_modules;
+use stats::count_runtime_and_type_only_deps;
+use stats::count_source_lines;
+use stats::find_graph_max_depth;
+use stats::find_largest_entries;
+use stats::find_npm_registry_hosts;
+use stats::find_subtree_sizes;
+use stats::module_needs_emit;
+use stats::summarize_modules_by_host;
+use stats::summarize_modules_by_media_type;
+use stats::write_largest_entries;
+use stats::write_summary_table;
+use stats::SplitSizes;
+use tree::print_tree_node;
+use tree::resolve_tree_width;
+use tree::tree_child_prefix;
+use tree::write_tree_child;
+use tree::write_tree_legend;
+use tree::write_tree_root;
+use tree::SpecifierAbbreviations;
+use tree::TreeConnectors;
+use tree::TreeNode;
+
+/// Version of the shape of the module graph JSON output. Consumers can
+/// branch on this field, so it must be incremented whenever a key is removed,
+/// renamed, or changes meaning. Every graph object has it, including the
+/// `--flat` output, the summary line of `--jsonl` and each graph of the array
+/// written for multiple modules.
+const JSON_OUTPUT_VERSION: u32 = 1;
+
+/// Exit code of `--lock-check` when the graph differs from the lockfile,
+/// which is distinct from the exit code of a failed integrity check.
+pub const LOCK_CHECK_EXIT_CODE: i32 = 11;
+
+/// Returned by `--lock-check` once the differences are reported, so the
+/// process exits with [`LOCK_CHECK_EXIT_CODE`] without cutting the output
+/// short or stopping `--watch`.
+#[derive(Debug)]
+pub struct LockCheckError {
+  count: usize,
+}
+
+impl fmt::Display for LockCheckError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} {} from the lock file.",
+      self.count,
+      if self.count == 1 {
+        "module differs"
+      } else {
+        "modules differ"
+      }
+    )
+  }
+}
+
+impl std::error::Error for LockCheckError {}
+
+pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
+  match info_flags.color {
+    InfoColorMode::Auto => {}
+    InfoColorMode::Always => colors::set_use_color(true),
+    InfoColorMode::Never => colors::set_use_color(false),
+  }
+  if let Some(watch_flags) = &info_flags.watch {
+    file_watcher::watch_func(
+      flags,
+      file_watcher::PrintConfig {
+        job_name: "Info".to_string(),
+        clear_screen: !watch_flags.no_clear_screen,
+      },
+      move |flags, sender, _changed_paths| {
+        let info_flags = info_flags.clone();
+        Ok(async move {
+          // the local modules of the graph are reported to the watcher when
+          // they're loaded
+          let factory = CliFactoryBuilder::new()
+            .with_watcher(sender.clone())
+            .build_from_flags(flags)
+            .await?;
+          let _ = sender.send(factory.cli_options().watch_paths());
+          info_action(factory, info_flags).await
+        })
+      },
+    )
+    .await
+  } else {
+    let factory = CliFactory::from_flags(flags).await?;
+    info_action(factory, info_flags).await
+  }
+}
+
+async fn info_action(
+  factory: CliFactory,
+  info_flags: InfoFlags,
+) -> Result<(), AnyError> {
+  let mut graph_error_count = 0;
+  match info_flags.file.as_slice() {
+    [] => {
+      let maybe_entrypoint = if info_flags.entrypoint_from_config {
+        find_config_entrypoint(factory.cli_options())?
+      } else {
+        None
+      };
+      match maybe_entrypoint {
+        Some(entrypoint) => {
+          info_module(
+            &factory,
+            &info_flags,
+            entrypoint.as_str(),
+            None,
+            &mut graph_error_count,
+          )
+          .await?;
+        }
+        // If it was just "deno info" print location of caches and exit
+        None => print_cache_info(
+          &factory,
+          &info_flags,
+          factory.cli_options().location_flag().as_ref(),
+        )?,
+      }
+    }
+    [specifier] => {
+      info_module(
+        &factory,
+        &info_flags,
+        specifier,
+        None,
+        &mut graph_error_count,
+      )
+      .await?;
+    }
+    specifiers => {
+      if specifiers.iter().any(|specifier| specifier == "-") {
+        bail!("Reading the module specifier from stdin can't be combined with other modules.");
+      }
+      if info_flags.out.is_some() {
+        bail!("--out can't be used with multiple modules.");
+      }
+      if info_flags.json {
+        let mut json_graphs = Vec::new();
+        for specifier in specifiers {
+          info_module(
+            &factory,
+            &info_flags,
+            specifier,
+            Some(&mut json_graphs),
+            &mut graph_error_count,
+          )
+          .await?;
+        }
+        write_json(&json!(json_graphs), &info_flags)?;
+      } else {
+        for (index, specifier) in specifiers.iter().enumerate() {
+          if index > 0 {
+            // separates the sections of the modules
+            write_output(b"\n", &info_flags)?;
+          }
+          info_module(
+            &factory,
+            &info_flags,
+            specifier,
+            None,
+            &mut graph_error_count,
+          )
+          .await?;
+        }
+      }
+    }
+  }
+  // the errors were already reported in the output, so failing here is
+  // what lets scripts and CI catch a broken graph. The watcher keeps
+  // running instead so they can be fixed.
+  if graph_error_count > 0 && info_flags.watch.is_none() {
+    bail!(
+      "Found {} module graph error{}.",
+      graph_error_count,
+      if graph_error_count == 1 { "" } else { "s" }
+    );
+  }
+  Ok(())
+}
+
+/// Shows the info of a single module. When `json_graphs` is provided, the
+/// json graph is collected in it instead of being written, so the graphs of
+/// multiple modules can be written as one array where each graph is keyed by
+/// the specifier of its module. The errors found in the
+/// graph are added to `graph_error_count` once the output was written.
+async fn info_module(
+  factory: &CliFactory,
+  info_flags: &InfoFlags,
+  specifier: &str,
+  json_graphs: Option<&mut Vec<serde_json::Value>>,
+  graph_error_count: &mut usize,
+) -> Result<(), AnyError> {
+  let cli_options = factory.cli_options();
+  let module_graph_builder = factory.module_graph_builder().await?;
+  let npm_resolver = factory.npm_resolver().await?;
+  let maybe_lockfile = factory.maybe_lockfile();
+  let maybe_import_map = factory.maybe_import_map().await?;
+  let specifier = if specifier == "-" {
+    read_specifier_from_stdin()?
+  } else {
+    specifier.to_string()
+  };
+  let specifier = resolve_url_or_path(&specifier, cli_options.initial_cwd())?;
+  let mut loader = module_graph_builder.create_graph_loader();
+  loader.enable_loading_cache_info(); // for displaying the cache information
+  let started = Instant::now();
+  let graph = if info_flags.cache_only {
+    // the npm packages aren't cached here so the missing ones can be listed
+    module_graph_builder
+      .create_graph_without_npm_caching(
+        info_flags.kind.as_graph_kind(),
+        vec![specifier.clone()],
+        &mut loader,
+      )
+      .await?
+  } else {
+    module_graph_builder
+      .create_graph_with_loader(
+        info_flags.kind.as_graph_kind(),
+        vec![specifier.clone()],
+        &mut loader,
+      )
+      .await?
+  };
+  let maybe_build_stats = if info_flags.verbose {
+    Some(GraphBuildStats::new(
+      &graph,
+      factory.file_fetcher()?,
+      started.elapsed(),
+    ))
+  } else {
+    None
+  };
+
+  if info_flags.cache_only {
+    let uncached = find_uncached_modules(&graph, npm_resolver);
+    if info_flags.json {
+      write_json(&json!({ "uncached": uncached }), info_flags)?;
+    } else {
+      let mut output = String::new();
+      write_uncached_modules(&uncached, &mut output)?;
+      write_output(output.as_bytes(), info_flags)?;
+    }
+    if !uncached.is_empty() {
+      bail!(
+        "{} {} not cached.",
+        uncached.len(),
+        if uncached.len() == 1 {
+          "module is"
+        } else {
+          "modules are"
+        }
+      );
+    }
+    return Ok(());
+  }
+
+  if info_flags.lock_check {
+    let Some(lockfile) = maybe_lockfile else {
+      bail!("--lock-check requires a lock file. Pass one with --lock or add a deno.json.");
+    };
+    let lockfile = lockfile.lock();
+    let diff = graph_lock_diff(&graph, &lockfile);
+    if info_flags.json {
+      let mismatched = diff
+        .mismatched
+        .iter()
+        .map(|(specifier, expected, actual)| {
+          json!({
+            "specifier": specifier,
+            "expected": expected,
+            "actual": actual,
+          })
+        })
+        .collect::<Vec<_>>();
+      write_json(
+        &json!({
+          "lockfile": lockfile.filename,
+          "mismatched": mismatched,
+          "missing": diff.missing,
+        }),
+        info_flags,
+      )?;
+    } else {
+      let mut output = String::new();
+      write_lockfile_diff(&diff, &lockfile.filename, &mut output)?;
+      write_output(output.as_bytes(), info_flags)?;
+    }
+    if !diff.is_empty() {
+      return Err(
+        LockCheckError {
+          count: diff.mismatched.len() + diff.missing.len(),
+        }
+        .into(),
+      );
+    }
+    return Ok(());
+  }
+
+  if let Some(lockfile) = maybe_lockfile {
+    graph_lock_or_exit(&graph, &mut lockfile.lock());
+  }
+
+  if info_flags.show_import_map && maybe_import_map.is_none() {
+    bail!("--show-import-map requires an import map. Pass one with --import-map or add one to the deno.json.");
+  }
+
+  if let Some(vendor_dir) = &info_flags.vendor_check {
+    let vendor_dir = cli_options.initial_cwd().join(vendor_dir);
+    if !vendor_dir.is_dir() {
+      bail!("Vendor directory not found: {}", vendor_dir.display());
+    }
+    let unvendored = find_unvendored_modules(&graph, &vendor_dir);
+    if info_flags.json {
+      write_json(
+        &json!({
+          "vendorDir": vendor_dir,
+          "unvendored": unvendored,
+        }),
+        info_flags,
+      )?;
+    } else {
+      let mut output = String::new();
+      write_unvendored_modules(&unvendored, &vendor_dir, &mut output)?;
+      write_output(output.as_bytes(), info_flags)?;
+    }
+    if !unvendored.is_empty() {
+      bail!(
+        "{} remote {} not vendored.",
+        unvendored.len(),
+        if unvendored.len() == 1 {
+          "module is"
+        } else {
+          "modules are"
+        }
+      );
+    }
+    return Ok(());
+  }
+
+  // re-root the graph so everything below only sees the focused subtree
+  let graph = match &info_flags.focus {
+    Some(focus) => {
+      let focus = resolve_url_or_path(focus, cli_options.initial_cwd())?;
+      let focus = graph.resolve(&focus);
+      if !matches!(graph.try_get(&focus), Ok(Some(_))) {
+        bail!("{} was not found in graph.", focus);
+      }
+      graph.segment(&[focus])
+    }
+    None => graph,
+  };
+
+  // the same options that type checking the graph would use
+  let maybe_ts_config = if info_flags.show_config {
+    let config_type = TsConfigType::Check {
+      lib: cli_options.ts_type_lib_window(),
+    };
+    Some(
+      cli_options
+        .resolve_ts_config_for_emit(config_type)?
+        .ts_config,
+    )
+  } else {
+    None
+  };
+  let maybe_config_specifier = cli_options
+    .maybe_config_file()
+    .as_ref()
+    .map(|config_file| &config_file.specifier);
+
+  if let Some(other) = &info_flags.diff {
+    let other = resolve_url_or_path(other, cli_options.initial_cwd())?;
+    let other_graph = module_graph_builder
+      .create_graph_with_loader(
+        info_flags.kind.as_graph_kind(),
+        vec![other],
+        &mut loader,
+      )
+      .await?;
+    return diff::write_graph_diff(
+      &graph,
+      &other_graph,
+      npm_resolver,
+      info_flags,
+    );
+  }
+
+  if info_flags.npm_usage {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = NpmInfo::build(&graph, npm_resolver, &npm_snapshot);
+    let usages = find_npm_package_usages(&graph, npm_resolver, &npm_info);
+    if info_flags.json {
+      let packages = usages
+        .iter()
+        .map(|usage| {
+          json!({
+            "package": usage.id.as_serialized(),
+            "size": usage.size,
+            "usedSize": usage.used_size,
+            "unusedSize": usage.unused_size(),
+          })
+        })
+        .collect::<Vec<_>>();
+      write_json(
+        &json!({
+          "unusedSize": usages
+            .iter()
+            .map(|usage| usage.unused_size())
+            .sum::<u64>(),
+          "packages": packages,
+        }),
+        info_flags,
+      )?;
+    } else {
+      let mut output = String::new();
+      write_npm_package_usages(&usages, &mut output)?;
+      write_output(output.as_bytes(), info_flags)?;
+    }
+    return Ok(());
+  }
+
+  if info_flags.cache_usage {
+    let http_cache = &factory.file_fetcher()?.http_cache;
+    let orphans = find_orphaned_cache_files(&graph, http_cache);
+    let reclaimable_size = orphans.iter().map(|(_, size)| size).sum::<u64>();
+    if info_flags.json {
+      let orphan_paths =
+        orphans.iter().map(|(path, _)| path).collect::<Vec<_>>();
+      write_json(
+        &json!({
+          "orphanCount": orphans.len(),
+          "reclaimableSize": reclaimable_size,
+          "orphans": orphan_paths,
+        }),
+        info_flags,
+      )?;
+    } else {
+      let mut output = String::new();
+      writeln!(
+        output,
+        "{} {}",
+        colors::bold("orphaned files:"),
+        orphans.len()
+      )?;
+      writeln!(
+        output,
+        "{} {}",
+        colors::bold("reclaimable size:"),
+        display::human_size(reclaimable_size as f64)
+      )?;
+      write_output(output.as_bytes(), info_flags)?;
+    }
+    return Ok(());
+  }
+
+  if let Some(specifier) = &info_flags.resolve_only {
+    let Some(resolved) =
+      resolve_in_graph(&graph, specifier, cli_options.initial_cwd())
+    else {
+      bail!("{} could not be resolved in the graph.", specifier);
+    };
+    let Ok(Some(module)) = graph.try_get(&resolved) else {
+      bail!("{} resolved to {}, which failed to load.", specifier, resolved);
+    };
+    let maybe_media_type = match module {
+      Module::Esm(module) => Some(module.media_type),
+      Module::Json(module) => Some(module.media_type),
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+    };
+    let maybe_local = match module {
+      Module::Esm(module) => module.maybe_cache_info.as_ref(),
+      Module::Json(module) => module.maybe_cache_info.as_ref(),
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+    }
+    .and_then(|cache_info| cache_info.local.as_ref());
+    let maybe_size = maybe_module_size(module);
+    if info_flags.json {
+      write_json(
+        &json!({
+          "specifier": module.specifier(),
+          "mediaType": maybe_media_type.map(|media_type| media_type.to_string()),
+          "size": maybe_size,
+          "local": maybe_local,
+        }),
+        info_flags,
+      )?;
+    } else {
+      let mut output = String::new();
+      writeln!(
+        output,
+        "{} {}",
+        colors::bold("specifier:"),
+        module.specifier()
+      )?;
+      if let Some(media_type) = maybe_media_type {
+        writeln!(output, "{} {}", colors::bold("type:"), media_type)?;
+      }
+      if let Some(size) = maybe_size {
+        writeln!(
+          output,
+          "{} {}",
+          colors::bold("size:"),
+          display::human_size(size as f64)
+        )?;
+      }
+      if let Some(local) = maybe_local {
+        writeln!(
+          output,
+          "{} {}",
+          colors::bold("local:"),
+          local.to_string_lossy()
+        )?;
+      }
+      write_output(output.as_bytes(), info_flags)?;
+    }
+    return Ok(());
+  }
+
+  if let Some(target) = &info_flags.importers {
+    let target = resolve_url_or_path(target, cli_options.initial_cwd())?;
+    return paths::write_importers(&graph, &target, info_flags);
+  }
+
+  if let Some(target) = &info_flags.path_to {
+    let target = resolve_url_or_path(target, cli_options.initial_cwd())?;
+    return paths::write_import_path(&graph, &target, info_flags);
+  }
+
+  if let Some(package) = &info_flags.why {
+    return paths::write_npm_package_chains(
+      &graph,
+      npm_resolver,
+      package,
+      info_flags,
+    );
+  }
+
+  if info_flags.by_host && !info_flags.json {
+    let mut output = String::new();
+    write_summary_table(
+      "host",
+      &summarize_modules_by_host(&graph),
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+    return Ok(());
+  }
+
+  if info_flags.stats && !info_flags.json {
+    let mut output = String::new();
+    write_summary_table(
+      "media type",
+      &summarize_modules_by_media_type(&graph),
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+    return Ok(());
+  }
+
+  if info_flags.csv {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = if info_flags.no_npm {
+      NpmInfo::default()
+    } else {
+      NpmInfo::build(&graph, npm_resolver, &npm_snapshot)
+    };
+    let mut output = String::new();
+    write_csv(&graph, &npm_info, &mut output)?;
+    write_output(output.as_bytes(), info_flags)?;
+    return Ok(());
+  }
+
+  if let Some(top) = info_flags.top.filter(|_| !info_flags.json) {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = if info_flags.no_npm {
+      NpmInfo::default()
+    } else {
+      NpmInfo::build(&graph, npm_resolver, &npm_snapshot)
+    };
+    let mut output = String::new();
+    write_largest_entries(
+      &find_largest_entries(&graph, &npm_info, top as usize),
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+    return Ok(());
+  }
+
+  if info_flags.treemap_json {
+    write_json(
+      &GraphDisplayContext::build_treemap_json(
+        &graph,
+        npm_resolver,
+        info_flags,
+      ),
+      info_flags,
+    )?;
+    return Ok(());
+  }
+
+  if info_flags.flat && !info_flags.json {
+    let mut output = String::new();
+    write_flat_list(
+      &graph,
+      info_flags.sort,
+      info_flags.format.as_deref(),
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+    return Ok(());
+  }
+
+  if info_flags.duplicates && !info_flags.json {
+    let mut output = String::new();
+    let checksums = ModuleChecksums::compute(&graph);
+    write_content_duplicates(
+      &find_content_duplicates(&graph, &checksums),
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+    return Ok(());
+  }
+
+  if info_flags.sbom {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = NpmInfo::build(&graph, npm_resolver, &npm_snapshot);
+    let checksums = ModuleChecksums::compute(&graph);
+    write_json(&build_sbom(&graph, &npm_info, &checksums), info_flags)?;
+    return Ok(());
+  }
+
+  if info_flags.json || info_flags.jsonl {
+    let mut json_graph = json!(graph);
+    add_npm_packages_to_json(&mut json_graph, npm_resolver);
+    add_cache_info_to_json(
+      &mut json_graph,
+      factory.file_fetcher()?,
+      info_flags.show_cache,
+    );
+    json_graph["totalLines"] = count_source_lines(&graph).into();
+    json_graph["maxDepth"] = find_graph_max_depth(&graph).into();
+    if info_flags.summary || info_flags.percent {
+      let npm_snapshot = npm_resolver.snapshot();
+      let npm_info = NpmInfo::build(&graph, npm_resolver, &npm_snapshot);
+      let total_size = graph
+        .modules()
+        .filter_map(maybe_module_size)
+        .chain(npm_info.package_sizes.values().copied())
+        .sum::<u64>();
+      if info_flags.summary {
+        json_graph["totalSize"] = total_size.into();
+      }
+      if info_flags.percent {
+        add_size_percentages_to_json(&mut json_graph, total_size);
+      }
+    }
+    if info_flags.split_sizes {
+      let npm_snapshot = npm_resolver.snapshot();
+      let npm_info = NpmInfo::build(&graph, npm_resolver, &npm_snapshot);
+      let sizes = SplitSizes::compute(&graph, &npm_info);
+      json_graph["rootSize"] = sizes.root.into();
+      json_graph["depsSize"] = sizes.deps.into();
+      json_graph["npmSize"] = sizes.npm.into();
+    }
+    let (local_count, remote_count) = count_local_and_remote_modules(&graph);
+    json_graph["localModules"] = local_count.into();
+    json_graph["remoteModules"] = remote_count.into();
+    json_graph["npmPackageCount"] = json_graph["npmPackages"]
+      .as_object()
+      .map_or(0, |packages| packages.len())
+      .into();
+    // shared by all the outputs that need the checksums of the sources
+    let checksums = ModuleChecksums::compute(&graph);
+    json_graph["uniqueContentCount"] = checksums.unique_count().into();
+    let (runtime_count, type_only_count) =
+      count_runtime_and_type_only_deps(&graph);
+    json_graph["runtimeDeps"] = runtime_count.into();
+    json_graph["typeOnlyDeps"] = type_only_count.into();
+    json_graph["emittedModules"] = graph
+      .modules()
+      .filter(|m| module_needs_emit(m))
+      .count()
+      .into();
+    json_graph["dynamicImports"] = count_dynamic_imports(&graph).into();
+    if info_flags.subtree_size {
+      add_subtree_sizes_to_json(&mut json_graph, &graph);
+    }
+    if info_flags.show_checksums {
+      add_checksums_to_json(&mut json_graph, &graph, &checksums);
+    }
+    add_dependency_flags_to_json(
+      &mut json_graph,
+      &graph,
+      maybe_import_map.as_deref(),
+    );
+    if info_flags.show_locations {
+      add_dependency_ranges_to_json(&mut json_graph, &graph);
+    }
+    mark_node_builtins_in_json(&mut json_graph);
+    add_jsr_packages_to_json(&mut json_graph);
+    let insecure_imports = find_insecure_imports(&graph);
+    if !insecure_imports.is_empty() {
+      json_graph["insecureImports"] = json!(insecure_imports);
+    }
+    if let Some(import_map) = maybe_import_map {
+      if info_flags.show_import_map {
+        json_graph["importMap"] =
+          find_used_import_map_entries(&graph, import_map).to_json();
+      }
+      let conflicts = find_import_map_npm_conflicts(&graph, import_map);
+      if !conflicts.is_empty() {
+        json_graph["importMapConflicts"] = conflicts
+          .into_iter()
+          .map(|conflict| {
+            json!({
+              "name": conflict.name,
+              "importMapVersions": conflict
+                .mapped_versions
+                .iter()
+                .map(|version| version.to_string())
+                .collect::<Vec<_>>(),
+              "directVersions": conflict
+                .direct_versions
+                .iter()
+                .map(|version| version.to_string())
+                .collect::<Vec<_>>(),
+            })
+          })
+          .collect::<Vec<_>>()
+          .into();
+      }
+    }
+    if info_flags.duplicates {
+      json_graph["contentDuplicates"] =
+        find_content_duplicates(&graph, &checksums)
+          .into_iter()
+          .map(|duplicate| {
+            json!({
+              "checksum": duplicate.checksum,
+              "specifiers": duplicate.specifiers,
+              "wastedSize": duplicate.wasted_size(),
+            })
+          })
+          .collect::<Vec<_>>()
+          .into();
+    }
+    json_graph["version"] = JSON_OUTPUT_VERSION.into();
+    if let Some(ts_config) = &maybe_ts_config {
+      json_graph["configFile"] =
+        json!(maybe_config_specifier.map(|specifier| specifier.as_str()));
+      json_graph["compilerOptions"] = ts_config.0.clone();
+    }
+    if info_flags.compressed {
+      json_graph["compressedSize"] = graph
+        .modules()
+        .filter_map(compressed_module_size)
+        .sum::<u64>()
+        .into();
+    }
+    if info_flags.by_host {
+      json_graph["byHost"] = summarize_modules_by_host(&graph)
+        .into_iter()
+        .map(|(host, summary)| {
+          json!({
+            "host": host,
+            "modules": summary.module_count,
+            "size": summary.size,
+          })
+        })
+        .collect::<Vec<_>>()
+        .into();
+    }
+    if info_flags.stats {
+      json_graph["mediaTypeStats"] = summarize_modules_by_media_type(&graph)
+        .into_iter()
+        .map(|(media_type, summary)| {
+          let summary = json!({
+            "modules": summary.module_count,
+            "size": summary.size,
+          });
+          (media_type, summary)
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into();
+    }
+    if let Some(top) = info_flags.top {
+      let npm_snapshot = npm_resolver.snapshot();
+      let npm_info = if info_flags.no_npm {
+        NpmInfo::default()
+      } else {
+        NpmInfo::build(&graph, npm_resolver, &npm_snapshot)
+      };
+      json_graph["top"] = find_largest_entries(&graph, &npm_info, top as usize)
+        .into_iter()
+        .map(|(specifier, size)| {
+          json!({
+            "specifier": specifier,
+            "size": size,
+          })
+        })
+        .collect::<Vec<_>>()
+        .into();
+    }
+    if info_flags.runtime_only {
+      let npm_snapshot = npm_resolver.snapshot();
+      let npm_info = NpmInfo::build(&graph, npm_resolver, &npm_snapshot);
+      let runtime_subgraph = RuntimeSubgraph::build(&graph, &npm_info);
+      let runtime_specifiers = runtime_subgraph
+        .modules
+        .iter()
+        .map(|specifier| specifier.as_str())
+        .collect::<HashSet<_>>();
+      if let Some(modules) = json_graph["modules"].as_array_mut() {
+        modules.retain(|module| {
+          module["specifier"]
+            .as_str()
+            .map_or(false, |specifier| runtime_specifiers.contains(specifier))
+        });
+      }
+      json_graph["excludedModules"] = runtime_subgraph.excluded_count.into();
+      json_graph["excludedSize"] = runtime_subgraph.excluded_size.into();
+    }
+    if let Some(json_graph) = json_graph.as_object_mut() {
+      if info_flags.npm_only {
+        json_graph.remove("modules");
+      }
+      if info_flags.no_npm {
+        json_graph.remove("npmPackages");
+        json_graph.remove("duplicateNpmPackages");
+      }
+      if info_flags.summary {
+        // only keep the aggregate fields
+        json_graph.remove("modules");
+        json_graph.remove("redirects");
+        json_graph.remove("npmPackages");
+      }
+    }
+    sort_json_modules(&mut json_graph);
+    if info_flags.jsonl {
+      write_json_lines(json_graph, info_flags)?;
+    } else {
+      // the modules of the json graph are already a flat list, so `--flat`
+      // keeps the versioned object
+      match json_graphs {
+        Some(json_graphs) => {
+          let mut keyed_graph = serde_json::Map::new();
+          keyed_graph.insert("specifier".to_string(), json!(specifier));
+          if let serde_json::Value::Object(json_graph) = json_graph {
+            keyed_graph.extend(json_graph);
+          }
+          json_graphs.push(keyed_graph.into());
+        }
+        None => write_json(&json_graph, info_flags)?,
+      }
+    }
+  } else if info_flags.dot {
+    let mut output = String::new();
+    GraphDisplayContext::write_dot(
+      &graph,
+      npm_resolver,
+      info_flags,
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+  } else if info_flags.mermaid {
+    let mut output = String::new();
+    GraphDisplayContext::write_mermaid(
+      &graph,
+      npm_resolver,
+      info_flags,
+      &mut output,
+    )?;
+    write_output(output.as_bytes(), info_flags)?;
+  } else {
+    if info_flags.no_dedup {
+      log::warn!(
+        "{} --no-dedup expands every path to the shared modules, so the output can be very large.",
+        colors::yellow("Warning")
+      );
+    }
+    let file_fetcher = factory.file_fetcher()?;
+    let mut writer = StreamWriter::new(info_flags)?;
+    let result = GraphDisplayContext::write(
+      &graph,
+      npm_resolver,
+      file_fetcher,
+      cli_options.initial_cwd(),
+      maybe_import_map.as_deref(),
+      info_flags,
+      &mut writer,
+    )
+    .and_then(|()| {
+      if let Some(ts_config) = &maybe_ts_config {
+        write_compiler_options(maybe_config_specifier, ts_config, &mut writer)?;
+      }
+      Ok(())
+    });
+    writer.finish(result)?;
+    if let Some(build_stats) = &maybe_build_stats {
+      // written to stderr so it doesn't end up in piped output
+      eprint!("{}", build_stats);
+    }
+  }
+  *graph_error_count += count_graph_errors(&graph, info_flags.no_npm);
+
+  if info_flags.fail_on_insecure {
+    let insecure_imports = find_insecure_imports(&graph);
+    if !insecure_imports.is_empty() {
+      bail!(
+        "{} module(s) are imported over plain http.",
+        insecure_imports.len()
+      );
+    }
+  }
+
+  if let Some(warn_size) = info_flags.warn_size {
+    let large_modules = find_large_modules(&graph, warn_size);
+    if info_flags.fail_on_warn && !large_modules.is_empty() {
+      bail!(
+        "{} module(s) are larger than {}.",
+        large_modules.len(),
+        display::human_size(warn_size as f64)
+      );
+    }
+  }
+  Ok(())
+}
+
+/// Finds the entrypoint of the project for `--entrypoint-from-config`, which
+/// is the module run by the `start` task of the deno.json or otherwise the
+/// `main` of the package.json.
+fn find_config_entrypoint(
+  cli_options: &CliOptions,
+) -> Result<Option<ModuleSpecifier>, AnyError> {
+  if let Some(config_file) = cli_options.maybe_config_file() {
+    let maybe_script = config_file
+      .to_tasks_config()?
+      .and_then(|tasks| tasks.get("start").cloned())
+      .and_then(|task| find_task_script(&task).map(ToString::to_string));
+    if let Some(script) = maybe_script {
+      return Ok(Some(config_file.specifier.join(&script)?));
+    }
+  }
+  if let Some(package_json) = cli_options.maybe_package_json() {
+    if let Some(main) = package_json.main(NodeModuleKind::Esm) {
+      return Ok(Some(package_json.specifier().join(main)?));
+    }
+  }
+  Ok(None)
+}
+
+/// Gets the module run by a `deno run` task, ex. `main.ts` for
+/// `deno run --allow-net main.ts`.
+fn find_task_script(task: &str) -> Option<&str> {
+  let mut args = task.split_whitespace();
+  if args.next()? != "deno" || args.next()? != "run" {
+    return None;
+  }
+  args.find(|arg| !arg.starts_with('-'))
+}
+
+/// Reads the specifier of the module to show from stdin for `deno info -`.
+fn read_specifier_from_stdin() -> Result<String, AnyError> {
+  let mut input = String::new();
+  std::io::stdin()
+    .read_to_string(&mut input)
+    .context("Failed to read the module specifier from stdin")?;
+  let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
+  match (lines.next(), lines.next()) {
+    (Some(specifier), None) => Ok(specifier.to_string()),
+    (None, _) => {
+      bail!("Expected a module specifier on stdin, but it was empty.")
+    }
+    (Some(_), Some(_)) => bail!(
+      "Expected a single module specifier on stdin, but found multiple lines."
+    ),
+  }
+}
+
+fn write_json(
+  value: &serde_json::Value,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  let Some(out) = &info_flags.out else {
+    return if info_flags.compact {
+      display::write_compact_json_to_stdout(value)
+    } else {
+      display::write_json_to_stdout(value)
+    };
+  };
+  let mut bytes = if info_flags.compact {
+    serde_json::to_vec(value)?
+  } else {
+    serde_json::to_vec_pretty(value)?
+  };
+  bytes.push(b'\n');
+  write_to_file(out, &bytes)
+}
+
+/// Writes each module of the json output as an object on its own line
+/// followed by a summary line with the remaining fields, which lets log
+/// pipelines consume the modules as they are written.
+fn write_json_lines(
+  mut json_graph: serde_json::Value,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  fn write_line(
+    writer: &mut StreamWriter,
+    line_type: &str,
+    value: serde_json::Value,
+  ) -> Result<(), AnyError> {
+    let mut line = serde_json::Map::new();
+    line.insert("type".to_string(), line_type.into());
+    if let serde_json::Value::Object(fields) = value {
+      line.extend(fields);
+    }
+    writeln!(writer, "{}", serde_json::to_string(&line)?)?;
+    Ok(())
+  }
+
+  let modules = match json_graph
+    .as_object_mut()
+    .and_then(|json_graph| json_graph.remove("modules"))
+  {
+    Some(serde_json::Value::Array(modules)) => modules,
+    _ => Vec::new(),
+  };
+  let mut writer = StreamWriter::new(info_flags)?;
+  let result = modules
+    .into_iter()
+    .try_for_each(|module| write_line(&mut writer, "module", module))
+    .and_then(|()| write_line(&mut writer, "summary", json_graph));
+  writer.finish(result)
+}
+
+fn write_output(bytes: &[u8], info_flags: &InfoFlags) -> Result<(), AnyError> {
+  match &info_flags.out {
+    Some(out) => write_to_file(out, bytes),
+    None => Ok(display::write_to_stdout_ignore_sigpipe(bytes)?),
+  }
+}
+
+/// Writes the text output as it's formatted instead of collecting it in a
+/// string first, which keeps the memory usage down for large graphs and lets
+/// the output stop early when the reader goes away (ex. `deno info | head`).
+struct StreamWriter {
+  inner: Box<dyn std::io::Write>,
+  error: Option<std::io::Error>,
+}
+
+impl StreamWriter {
+  fn new(info_flags: &InfoFlags) -> Result<Self, AnyError> {
+    let inner: Box<dyn std::io::Write> = match &info_flags.out {
+      Some(out) => {
+        let file = File::create(out).with_context(|| {
+          format!("Failed to create output file {}", out.display())
+        })?;
+        Box::new(BufWriter::new(file))
+      }
+      None => Box::new(BufWriter::new(std::io::stdout())),
+    };
+    Ok(Self { inner, error: None })
+  }
+
+  /// Flushes the output and surfaces the error that stopped the writing,
+  /// ignoring that the reader went away.
+  fn finish(mut self, result: Result<(), AnyError>) -> Result<(), AnyError> {
+    if self.error.is_none() {
+      self.error = self.inner.flush().err();
+    }
+    match self.error {
+      Some(err) if err.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+      Some(err) => Err(err.into()),
+      None => result,
+    }
+  }
+}
+
+impl fmt::Write for StreamWriter {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    if self.error.is_some() {
+      return Err(fmt::Error);
+    }
+    self.inner.write_all(s.as_bytes()).map_err(|err| {
+      self.error = Some(err);
+      fmt::Error
+    })
+  }
+}
+
+fn write_to_file(path: &Path, bytes: &[u8]) -> Result<(), AnyError> {
+  let mut file = File::create(path).with_context(|| {
+    format!("Failed to create output file {}", path.display())
+  })?;
+  file
+    .write_all(bytes)
+    .with_context(|| format!("Failed to write output file {}", path.display()))
+}
+
+fn print_cache_info(
+  factory: &CliFactory,
+  info_flags: &InfoFlags,
+  location: Option<&deno_core::url::Url>,
+) -> Result<(), AnyError> {
+  let dir = factory.deno_dir()?;
+  let modules_cache = factory.file_fetcher()?.get_http_cache_location();
+  let npm_cache = factory.npm_cache()?.as_readonly().get_cache_location();
+  let typescript_cache = &dir.gen_cache.location;
+  let registry_cache = dir.registries_folder_path();
+  let mut origin_dir = dir.origin_data_folder_path();
+  let deno_dir = dir.root_path_for_display().to_string();
+
+  if let Some(location) = &location {
+    origin_dir =
+      origin_dir.join(checksum::gen(&[location.to_string().as_bytes()]));
+  }
+
+  let local_storage_dir = origin_dir.join("local_storage");
+
+  if info_flags.json {
+    let mut output = json!({
+      "denoDir": deno_dir,
+      "modulesCache": modules_cache,
+      "npmCache": npm_cache,
+      "typescriptCache": typescript_cache,
+      "registryCache": registry_cache,
+      "originStorage": origin_dir,
+    });
+
+    if location.is_some() {
+      output["localStorage"] = serde_json::to_value(local_storage_dir)?;
+    }
+
+    if info_flags.sizes {
+      // walking the directories can be slow, so only do it when requested
+      let sizes = output
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, path)| {
+          let path = Path::new(path.as_str()?);
+          // directories that weren't created yet are treated as empty
+          let size = dir_size(path).unwrap_or(0);
+          Some((key.clone(), size.into()))
+        })
+        .collect::<serde_json::Map<_, _>>();
+      output["sizes"] = sizes.into();
+    }
+
+    write_json(&output, info_flags)
+  } else {
+    println!("{} {}", colors::bold("DENO_DIR location:"), deno_dir);
+    println!(
+      "{} {}",
+      colors::bold("Remote modules cache:"),
+      modules_cache.display()
+    );
+    println!(
+      "{} {}",
+      colors::bold("npm modules cache:"),
+      npm_cache.display()
+    );
+    println!(
+      "{} {}",
+      colors::bold("Emitted modules cache:"),
+      typescript_cache.display()
+    );
+    println!(
+      "{} {}",
+      colors::bold("Language server registries cache:"),
+      registry_cache.display(),
+    );
+    println!(
+      "{} {}",
+      colors::bold("Origin storage:"),
+      origin_dir.display()
+    );
+    if location.is_some() {
+      println!(
+        "{} {}",
+        colors::bold("Local Storage:"),
+        local_storage_dir.display(),
+      );
+    }
+    Ok(())
+  }
+}
+
+fn write_compiler_options<TWrite: Write>(
+  maybe_config_specifier: Option<&ModuleSpecifier>,
+  ts_config: &TsConfig,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  writeln!(writer)?;
+  match maybe_config_specifier {
+    Some(specifier) => {
+      writeln!(writer, "{} {}", colors::bold("config:"), specifier)?
+    }
+    None => writeln!(
+      writer,
+      "{} {}",
+      colors::bold("config:"),
+      colors::gray("none")
+    )?,
+  }
+  writeln!(writer, "{}", colors::bold("compiler options:"))?;
+  if let Some(options) = ts_config.0.as_object() {
+    let options = options.iter().collect::<BTreeMap<_, _>>();
+    for (name, value) in options {
+      writeln!(writer, "  {}: {}", name, value)?;
+    }
+  }
+  Ok(())
+}
+
+/// How long it took to build the module graph and where the remote modules
+/// were loaded from, shown with `--verbose`.
+struct GraphBuildStats {
+  elapsed: Duration,
+  downloaded_count: usize,
+  cached_count: usize,
+}
+
+impl GraphBuildStats {
+  fn new(
+    graph: &ModuleGraph,
+    file_fetcher: &FileFetcher,
+    elapsed: Duration,
+  ) -> Self {
+    let (downloaded, cached): (Vec<_>, Vec<_>) = graph
+      .modules()
+      .map(|module| module.specifier())
+      .filter(|specifier| matches!(specifier.scheme(), "http" | "https"))
+      .partition(|specifier| file_fetcher.was_downloaded(specifier));
+    Self {
+      elapsed,
+      downloaded_count: downloaded.len(),
+      cached_count: cached.len(),
+    }
+  }
+}
+
+impl fmt::Display for GraphBuildStats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      f,
+      "{} {}",
+      colors::bold("graph built in:"),
+      display::human_elapsed(self.elapsed.as_millis()),
+    )?;
+    writeln!(
+      f,
+      "{} {} downloaded, {} from the cache",
+      colors::bold("remote modules:"),
+      self.downloaded_count,
+      self.cached_count,
+    )
+  }
+}
+
+/// Resolves a specifier the way it's resolved by the modules of the graph,
+/// so bare specifiers mapped by the import map work. Specifiers that aren't
+/// imported by any module are resolved as a path or URL instead.
+fn resolve_in_graph(
+  graph: &ModuleGraph,
+  specifier: &str,
+  initial_cwd: &Path,
+) -> Option<ModuleSpecifier> {
+  // the roots come first, so their imports take precedence
+  let roots = graph.roots.iter().map(|root| graph.resolve(root));
+  let modules = roots
+    .filter_map(|root| graph.get(&root))
+    .chain(graph.modules());
+  for module in modules {
+    let Some(module) = module.esm() else {
+      continue;
+    };
+    if let Some(dep) = module.dependencies.get(specifier) {
+      if let Some(resolved) = dep.get_code().or_else(|| dep.get_type()) {
+        return Some(graph.resolve(resolved));
+      }
+    }
+  }
+  let resolved = resolve_url_or_path(specifier, initial_cwd).ok()?;
+  let resolved = graph.resolve(&resolved);
+  match graph.try_get(&resolved) {
+    Ok(None) => None,
+    Ok(Some(_)) | Err(_) => Some(resolved),
+  }
+}
+
+/// Counts the modules that failed to load or resolve, including the ones only
+/// reachable through type or dynamic imports.
+fn count_graph_errors(graph: &ModuleGraph, no_npm: bool) -> usize {
+  graph
+    .walk(
+      &graph.roots,
+      deno_graph::WalkOptions {
+        check_js: true,
+        follow_type_only: true,
+        follow_dynamic: true,
+      },
+    )
+    .errors()
+    .filter(|error| match error {
+      // npm specifiers fail to load when npm resolution is disabled
+      ModuleGraphError::ModuleError(error) => {
+        !(no_npm && error.specifier().scheme() == "npm")
+      }
+      ModuleGraphError::ResolutionError(_) => true,
+    })
+    .count()
+}
+
+/// The checksums of the sources of the modules in the graph, computed the
+/// same way as the checksums stored in the lockfile. They are computed once
+/// and shared between the outputs that need them.
+pub struct ModuleChecksums<'a>(HashMap<&'a ModuleSpecifier, String>);
+
+impl<'a> ModuleChecksums<'a> {
+  fn compute(graph: &'a ModuleGraph) -> Self {
+    let checksums = graph
+      .modules()
+      .filter_map(|module| {
+        let source = match module {
+          Module::Esm(module) => &module.source,
+          Module::Json(module) => &module.source,
+          Module::Node(_) | Module::Npm(_) | Module::External(_) => {
+            return None
+          }
+        };
+        Some((module.specifier(), checksum::gen(&[source.as_bytes()])))
+      })
+      .collect();
+    Self(checksums)
+  }
+
+  /// Gets the checksum of a remote ESM module.
+  fn remote(&self, module: &Module) -> Option<&str> {
+    let module = module.esm()?;
+    if !matches!(module.specifier.scheme(), "http" | "https") {
+      return None;
+    }
+    self
+      .0
+      .get(&module.specifier)
+      .map(|checksum| checksum.as_str())
+  }
+
+  /// Gets the number of distinct sources, which is lower than the number of
+  /// modules when the same content was loaded from multiple specifiers.
+  fn unique_count(&self) -> usize {
+    self.0.values().collect::<HashSet<_>>().len()
+  }
+
+  fn module_count(&self) -> usize {
+    self.0.len()
+  }
+}
+
+fn cache_state_text(
+  file_fetcher: &FileFetcher,
+  specifier: &ModuleSpecifier,
+) -> &'static str {
+  if file_fetcher.was_downloaded(specifier) {
+    "fetched"
+  } else {
+    "cached"
+  }
+}
+
+/// Gets the specifiers of the successfully resolved code and type
+/// dependencies of a module.
+fn esm_dependency_specifiers(
+  module: &EsmModule,
+) -> impl Iterator<Item = &ModuleSpecifier> {
+  let types_resolution = module
+    .maybe_types_dependency
+    .as_ref()
+    .map(|dep| &dep.dependency);
+  types_resolution
+    .into_iter()
+    .chain(
+      module
+        .dependencies
+        .values()
+        .flat_map(|dep| [&dep.maybe_code, &dep.maybe_type]),
+    )
+    .filter_map(|resolution| match resolution {
+      Resolution::Ok(resolved) => Some(&resolved.specifier),
+      Resolution::Err(_) | Resolution::None => None,
+    })
+}
+
+/// Finds the modules that are loaded at runtime, which excludes the ones only
+/// reachable through type-only dependencies.
+fn find_runtime_modules(graph: &ModuleGraph) -> HashSet<ModuleSpecifier> {
+  graph
+    .walk(
+      &graph.roots,
+      deno_graph::WalkOptions {
+        check_js: true,
+        follow_type_only: false,
+        follow_dynamic: true,
+      },
+    )
+    .map(|(specifier, _)| specifier.clone())
+    .collect()
+}
+
+/// The subgraph of the modules that are loaded at runtime, shown with
+/// `--runtime-only`. Unlike `--no-type-deps`, which only hides the type-only
+/// dependencies, the `@types/*` npm packages are left out as well and the
+/// modules that were left out are counted.
+struct RuntimeSubgraph {
+  modules: HashSet<ModuleSpecifier>,
+  excluded_count: usize,
+  excluded_size: u64,
+}
+
+impl RuntimeSubgraph {
+  fn build(graph: &ModuleGraph, npm_info: &NpmInfo) -> Self {
+    let is_types_package = |module: &Module| {
+      module
+        .npm()
+        .map_or(false, |npm| npm.nv_reference.nv.name.starts_with("@types/"))
+    };
+    let mut modules = find_runtime_modules(graph);
+    modules.retain(|specifier| {
+      !matches!(graph.get(specifier), Some(module) if is_types_package(module))
+    });
+    let mut excluded_count = 0;
+    let mut excluded_size = 0;
+    for module in graph.modules() {
+      if modules.contains(module.specifier()) {
+        continue;
+      }
+      excluded_count += 1;
+      excluded_size += match module.npm() {
+        Some(npm) => npm_info
+          .resolve_package(&npm.nv_reference.nv)
+          .and_then(|package| npm_info.package_sizes.get(&package.id))
+          .copied(),
+        None => maybe_module_size(module),
+      }
+      .unwrap_or(0);
+    }
+    Self {
+      modules,
+      excluded_count,
+      excluded_size,
+    }
+  }
+}
+
+/// Finds the modules whose specifier matches the filter along with the
+/// modules that transitively import them, which are needed to reach the
+/// matching modules from the roots.
+fn find_filtered_modules(
+  graph: &ModuleGraph,
+  filter: &GlobPattern,
+) -> HashSet<ModuleSpecifier> {
+  let mut importers: HashMap<ModuleSpecifier, Vec<&ModuleSpecifier>> =
+    HashMap::new();
+  for module in graph.modules().filter_map(|module| module.esm()) {
+    for specifier in esm_dependency_specifiers(module) {
+      importers
+        .entry(graph.resolve(specifier))
+        .or_default()
+        .push(&module.specifier);
+    }
+  }
+  let mut pending = graph
+    .modules()
+    .map(|module| module.specifier())
+    .filter(|specifier| filter.matches_str(specifier.as_str()))
+    .cloned()
+    .collect::<Vec<_>>();
+  let mut visible = pending.iter().cloned().collect::<HashSet<_>>();
+  while let Some(specifier) = pending.pop() {
+    for importer in importers.get(&specifier).into_iter().flatten() {
+      if visible.insert((*importer).clone()) {
+        pending.push((*importer).clone());
+      }
+    }
+  }
+  visible
+}
+
+fn maybe_module_size(module: &Module) -> Option<u64> {
+  match module {
+    Module::Esm(module) => Some(module.size() as u64),
+    Module::Json(module) => Some(module.size() as u64),
+    Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+  }
+}
+
+/// Estimates the size of the source of an ESM or JSON module after gzip
+/// compression, which is closer to what is transferred over the wire.
+fn compressed_module_size(module: &Module) -> Option<u64> {
+  let source = match module {
+    Module::Esm(module) => &module.source,
+    Module::Json(module) => &module.source,
+    Module::Node(_) | Module::Npm(_) | Module::External(_) => return None,
+  };
+  let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+  encoder.write_all(source.as_bytes()).ok()?;
+  Some(encoder.finish().ok()?.len() as u64)
+}
+
+/// An npm package that resolves to different versions through the import
+/// map than through the `npm:` specifiers that are imported directly.
+struct ImportMapNpmConflict {
+  name: String,
+  mapped_versions: BTreeSet<Version>,
+  direct_versions: BTreeSet<Version>,
+}
+
+/// Finds the npm packages that are both mapped in the import map and
+/// imported directly, but where the two don't resolve to the same versions.
+fn find_import_map_npm_conflicts(
+  graph: &ModuleGraph,
+  import_map: &ImportMap,
+) -> Vec<ImportMapNpmConflict> {
+  let mut versions_by_name =
+    BTreeMap::<&str, (BTreeSet<Version>, BTreeSet<Version>)>::new();
+  for module in graph.modules() {
+    let Some(module) = module.esm() else {
+      continue;
+    };
+    for (raw_specifier, dep) in &module.dependencies {
+      let Some(specifier) = dep.get_code() else {
+        continue;
+      };
+      let is_direct = raw_specifier.starts_with("npm:");
+      let is_mapped = !is_direct
+        && import_map
+          .resolve(raw_specifier, &module.specifier)
+          .map_or(false, |mapped| &mapped == specifier);
+      if !is_direct && !is_mapped {
+        continue;
+      }
+      let Some(Module::Npm(npm_module)) = graph.get(specifier) else {
+        continue;
+      };
+      let nv = &npm_module.nv_reference.nv;
+      let (mapped_versions, direct_versions) =
+        versions_by_name.entry(nv.name.as_str()).or_default();
+      if is_direct {
+        direct_versions.insert(nv.version.clone());
+      } else {
+        mapped_versions.insert(nv.version.clone());
+      }
+    }
+  }
+  versions_by_name
+    .into_iter()
+    .filter(|(_, (mapped_versions, direct_versions))| {
+      !mapped_versions.is_empty()
+        && !direct_versions.is_empty()
+        && mapped_versions != direct_versions
+    })
+    .map(
+      |(name, (mapped_versions, direct_versions))| ImportMapNpmConflict {
+        name: name.to_string(),
+        mapped_versions,
+        direct_versions,
+      },
+    )
+    .collect()
+}
+
+/// The entries of an import map that were used to resolve the modules of the
+/// graph, shown with `--show-import-map`. The entries are keyed by their raw
+/// key and the scoped entries are grouped by the raw key of the scope, the
+/// same as in the import map itself.
+struct UsedImportMapEntries {
+  import_map_specifier: ModuleSpecifier,
+  imports: BTreeMap<String, String>,
+  scopes: BTreeMap<String, BTreeMap<String, String>>,
+  total_count: usize,
+}
+
+impl UsedImportMapEntries {
+  fn used_count(&self) -> usize {
+    self.imports.len() + self.scopes.values().map(|s| s.len()).sum::<usize>()
+  }
+
+  fn to_json(&self) -> serde_json::Value {
+    json!({
+      "specifier": self.import_map_specifier,
+      "imports": self.imports,
+      "scopes": self.scopes,
+    })
+  }
+}
+
+/// Finds the import map entries that were used to resolve the dependencies of
+/// the graph. A dependency is resolved with the import map when resolving it
+/// without the import map gives a different module or fails.
+fn find_used_import_map_entries(
+  graph: &ModuleGraph,
+  import_map: &ImportMap,
+) -> UsedImportMapEntries {
+  /// Finds the entry of a specifier map that matches a specifier, which is
+  /// either an exact match or the longest matching prefix.
+  fn find_entry(
+    specifier_map: &SpecifierMap,
+    specifier: &str,
+  ) -> Option<(String, String)> {
+    specifier_map
+      .entries()
+      .filter(|entry| {
+        entry.key == specifier
+          || (entry.key.ends_with('/') && specifier.starts_with(entry.key))
+      })
+      .max_by_key(|entry| entry.key.len())
+      .and_then(|entry| {
+        Some((entry.raw_key.to_string(), entry.raw_value?.to_string()))
+      })
+  }
+
+  let mut used = UsedImportMapEntries {
+    import_map_specifier: import_map.base_url().clone(),
+    total_count: import_map.imports().entries().count()
+      + import_map
+        .scopes()
+        .map(|scope| scope.imports.entries().count())
+        .sum::<usize>(),
+    imports: BTreeMap::new(),
+    scopes: BTreeMap::new(),
+  };
+  for module in graph.modules() {
+    let Some(module) = module.esm() else {
+      continue;
+    };
+    let referrer = &module.specifier;
+    for raw_specifier in module.dependencies.keys() {
+      let Ok(mapped) = import_map.resolve(raw_specifier, referrer) else {
+        continue;
+      };
+      let maybe_unmapped =
+        resolve_import(raw_specifier, referrer.as_str()).ok();
+      if maybe_unmapped.as_ref() == Some(&mapped) {
+        continue; // not affected by the import map
+      }
+      // the keys of the import map are normalized the same way
+      let specifier = maybe_unmapped
+        .as_ref()
+        .map(|specifier| specifier.as_str())
+        .unwrap_or(raw_specifier);
+      // the scopes are sorted from the most to the least specific
+      let maybe_scoped_entry = import_map
+        .scopes()
+        .filter(|scope| {
+          scope.key == referrer.as_str()
+            || (scope.key.ends_with('/')
+              && referrer.as_str().starts_with(scope.key))
+        })
+        .find_map(|scope| {
+          find_entry(scope.imports, specifier)
+            .map(|entry| (scope.raw_key.to_string(), entry))
+        });
+      if let Some((scope, (key, value))) = maybe_scoped_entry {
+        used.scopes.entry(scope).or_default().insert(key, value);
+      } else if let Some((key, value)) =
+        find_entry(import_map.imports(), specifier)
+      {
+        used.imports.insert(key, value);
+      }
+    }
+  }
+  used
+}
+
+fn join_versions(versions: &BTreeSet<Version>) -> String {
+  versions
+    .iter()
+    .map(|version| version.to_string())
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// A node of the tree that's only built when it's written, so the tree is
+/// never held in memory as a whole.
+enum PendingNode<'a> {
+  Dependency {
+    resolution: &'a Resolution,
+    type_dep: bool,
+    /// The importing module, which the location of the import is shown for.
+    maybe_referrer: Option<&'a ModuleSpecifier>,
+    is_dynamic: bool,
+    is_mapped: bool,
+  },
+  NpmPackage {
+    id: NpmPackageId,
+    is_peer: bool,
+    maybe_suffix: Option<String>,
+  },
+  Text(String),
+}
+
+/// The text of a node along with its children that are yet to be built.
+struct BuiltNode<'a> {
+  text: String,
+  children: Vec<PendingNode<'a>>,
+  /// Key of the expanded module or package, which is an ancestor of its
+  /// children while they're built.
+  maybe_ancestor_key: Option<String>,
+}
+
+impl<'a> BuiltNode<'a> {
+  fn from_text(text: String) -> Self {
+    Self {
+      text,
+      children: Default::default(),
+      maybe_ancestor_key: None,
+    }
+  }
+}
+
+/// Counts the nodes of the tree while it's written, so the ones past
+/// `--max-nodes` are left out.
+struct TreeProgress {
+  max_nodes: Option<usize>,
+  total_nodes: usize,
+}
+
+impl TreeProgress {
+  /// Counts a node and returns whether it's written.
+  fn add_node(&mut self) -> bool {
+    self.total_nodes += 1;
+    self
+      .max_nodes
+      .map_or(true, |max_nodes| self.total_nodes <= max_nodes)
+  }
+
+  fn is_full(&self) -> bool {
+    matches!(self.max_nodes, Some(max_nodes) if self.total_nodes >= max_nodes)
+  }
+}
+
+struct GraphDisplayContext<'a> {
+  graph: &'a ModuleGraph,
+  npm_info: NpmInfo,
+  /// Keys of the modules and packages that were displayed, along with the
+  /// key of the parent they were first displayed under.
+  seen: HashMap<String, Option<String>>,
+  /// Keys of the modules and packages on the path from the root to the
+  /// nodes currently being built.
+  ancestors: Vec<String>,
+  /// Distinct cycles found while building the tree.
+  cycles: HashSet<Vec<String>>,
+  /// Depth in the tree of the nodes currently being built.
+  depth: u32,
+  max_depth: Option<u32>,
+  npm_only: bool,
+  no_npm: bool,
+  sort: InfoSortOrder,
+  show_licenses: bool,
+  show_npm_urls: bool,
+  show_dep_counts: bool,
+  connectors: TreeConnectors,
+  /// Number of columns the lines of the tree are shortened to.
+  tree_width: Option<usize>,
+  /// Modules reachable without following type-only dependencies when
+  /// using `--no-type-deps`.
+  runtime_modules: Option<HashSet<ModuleSpecifier>>,
+  show_types: bool,
+  verbose_dedup: bool,
+  /// Expands repeated modules and packages with `--no-dedup`, only stopping
+  /// at cycles.
+  no_dedup: bool,
+  compressed: bool,
+  /// Cached compressed sizes of the modules, keyed by specifier.
+  compressed_sizes: HashMap<ModuleSpecifier, u64>,
+  /// Modules left in the tree after applying `--filter`.
+  visible_modules: Option<HashSet<ModuleSpecifier>>,
+  /// Used to show the cache state of remote modules with `--show-cache`.
+  file_fetcher: Option<&'a FileFetcher>,
+  /// Modules hidden from the tree with `--exclude`.
+  exclude: Option<GlobSet>,
+  exclude_from_totals: bool,
+  /// Precomputed sizes of the modules along with their transitive imports
+  /// when using `--subtree-size`.
+  subtree_sizes: Option<HashMap<ModuleSpecifier, u64>>,
+  show_checksums: bool,
+  /// Checksums of the sources, which are only computed once the header
+  /// that shows the unique content is written.
+  checksums: Option<ModuleChecksums<'a>>,
+  roots_only: bool,
+  color_by_type: bool,
+  summary: bool,
+  /// Modules larger than this are marked in the tree with `--warn-size`.
+  warn_size: Option<u64>,
+  show_emit: bool,
+  show_locations: bool,
+  percent: bool,
+  /// Total sizes that the sizes are compared against with `--percent`, which
+  /// are set once they're computed. npm packages are always compared
+  /// against the size of both the modules and the npm packages.
+  percent_totals: Option<(f64, f64)>,
+  /// Shortened prefixes of the remote specifiers with `--abbreviate`. Only
+  /// the displayed text is shortened, the `seen` keys stay the full ones.
+  abbreviations: Option<SpecifierAbbreviations>,
+  /// Number of tree nodes to display before truncating with `--max-nodes`.
+  max_nodes: Option<usize>,
+  /// Directory that local specifiers are displayed relative to with
+  /// `--relative`. Like the abbreviations, this only changes the text.
+  relative_to: Option<ModuleSpecifier>,
+  count_only: bool,
+  size_only: bool,
+  /// Shows the npm packages under the directly imported package that pulled
+  /// them in with `--group-npm-by-root`.
+  group_npm_by_root: bool,
+  /// Splits the total size into the roots, their dependencies and the npm
+  /// packages with `--split-sizes`.
+  split_sizes: bool,
+  import_map_conflicts: Vec<ImportMapNpmConflict>,
+  /// The import map entries shown with `--show-import-map`.
+  used_import_map_entries: Option<UsedImportMapEntries>,
+  /// Number and size of the modules left out with `--runtime-only`.
+  runtime_excluded: Option<(usize, u64)>,
+  /// Explains the tree with `--legend` or `--verbose`.
+  legend: bool,
+  /// Marks the dependencies that it remapped.
+  import_map: Option<&'a ImportMap>,
+}
+
+impl<'a> GraphDisplayContext<'a> {
+  fn new(
+    graph: &'a ModuleGraph,
+    npm_resolver: &'a CliNpmResolver,
+    info_flags: &InfoFlags,
+  ) -> Self {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = if info_flags.no_npm {
+      NpmInfo::default()
+    } else {
+      NpmInfo::build(graph, npm_resolver, &npm_snapshot)
+    };
+    Self {
+      graph,
+      npm_info,
+      seen: Default::default(),
+      ancestors: Default::default(),
+      cycles: Default::default(),
+      depth: 0,
+      // only the direct dependencies are shown for `--roots-only`
+      max_depth: if info_flags.roots_only {
+        Some(1)
+      } else {
+        info_flags.depth
+      },
+      // the groups only contain the npm packages
+      npm_only: info_flags.npm_only || info_flags.group_npm_by_root,
+      group_npm_by_root: info_flags.group_npm_by_root,
+      split_sizes: info_flags.split_sizes,
+      no_npm: info_flags.no_npm,
+      sort: info_flags.sort,
+      show_licenses: info_flags.show_licenses,
+      show_npm_urls: info_flags.show_npm_urls,
+      show_dep_counts: info_flags.show_dep_counts,
+      connectors: TreeConnectors::from_flags(info_flags),
+      tree_width: resolve_tree_width(info_flags),
+      runtime_modules: info_flags
+        .no_type_deps
+        .then(|| find_runtime_modules(graph)),
+      show_types: info_flags.show_types,
+      verbose_dedup: info_flags.verbose_dedup,
+      no_dedup: info_flags.no_dedup,
+      compressed: info_flags.compressed,
+      compressed_sizes: Default::default(),
+      visible_modules: None,
+      file_fetcher: None,
+      exclude: None,
+      exclude_from_totals: info_flags.exclude_from_totals,
+      subtree_sizes: info_flags.subtree_size.then(|| find_subtree_sizes(graph)),
+      show_checksums: info_flags.show_checksums,
+      checksums: None,
+      roots_only: info_flags.roots_only,
+      color_by_type: info_flags.color_by_type,
+      summary: info_flags.summary,
+      warn_size: info_flags.warn_size,
+      show_emit: info_flags.show_emit,
+      show_locations: info_flags.show_locations,
+      percent: info_flags.percent,
+      percent_totals: None,
+      abbreviations: info_flags
+        .abbreviate
+        .then(|| SpecifierAbbreviations::build(graph)),
+      max_nodes: info_flags.max_nodes.map(|max_nodes| max_nodes as usize),
+      relative_to: None,
+      count_only: info_flags.count_only,
+      size_only: info_flags.size_only,
+      import_map_conflicts: Vec::new(),
+      used_import_map_entries: None,
+      runtime_excluded: None,
+      legend: info_flags.legend || info_flags.verbose,
+      import_map: None,
+    }
+  }
+
+  pub fn write<TWrite: Write>(
+    graph: &'a ModuleGraph,
+    npm_resolver: &'a CliNpmResolver,
+    file_fetcher: &'a FileFetcher,
+    initial_cwd: &Path,
+    maybe_import_map: Option<&'a ImportMap>,
+    info_flags: &InfoFlags,
+    writer: &mut TWrite,
+  ) -> Result<(), AnyError> {
+    let mut context = Self::new(graph, npm_resolver, info_flags);
+    context.import_map = maybe_import_map;
+    if let Some(import_map) = maybe_import_map {
+      context.import_map_conflicts =
+        find_import_map_npm_conflicts(graph, import_map);
+      if info_flags.show_import_map {
+        context.used_import_map_entries =
+          Some(find_used_import_map_entries(graph, import_map));
+      }
+    }
+    if info_flags.relative {
+      context.relative_to =
+        ModuleSpecifier::from_directory_path(initial_cwd).ok();
+    }
+    if info_flags.show_cache {
+      context.file_fetcher = Some(file_fetcher);
+    }
+    if let Some(filter) = &info_flags.filter {
+      let filter = GlobPattern::new(filter)?;
+      context.visible_modules = Some(find_filtered_modules(graph, &filter));
+    }
+    if info_flags.runtime_only {
+      // only the runtime subgraph is shown and counted
+      let runtime_subgraph = RuntimeSubgraph::build(graph, &context.npm_info);
+      context.visible_modules = Some(match context.visible_modules.take() {
+        Some(visible_modules) => visible_modules
+          .intersection(&runtime_subgraph.modules)
+          .cloned()
+          .collect(),
+        None => runtime_subgraph.modules,
+      });
+      context.runtime_excluded = Some((
+        runtime_subgraph.excluded_count,
+        runtime_subgraph.excluded_size,
+      ));
+    }
+    if !info_flags.exclude.is_empty() {
+      let patterns = info_flags
+        .exclude
+        .iter()
+        .map(|pattern| GlobPattern::new(pattern))
+        .collect::<Result<Vec<_>, _>>()?;
+      context.exclude = Some(GlobSet::new(patterns));
+    }
+    context.into_writer(writer)?;
+    Ok(())
+  }
+
+  fn into_writer<TWrite: Write>(mut self, writer: &mut TWrite) -> fmt::Result {
+    if self.graph.roots.is_empty() || self.graph.roots.len() > 1 {
+      return writeln!(
+        writer,
+        "{} displaying graphs that have multiple roots is not supported.",
+        colors::red("error:")
+      );
+    }
+
+    let graph = self.graph;
+    let requested_specifier = &graph.roots[0];
+    let root_specifier = graph.resolve(requested_specifier);
+    let root = match graph.try_get(&root_specifier) {
+      Ok(Some(root)) => root,
+      Err(ModuleGraphError::ModuleError(ModuleError::Missing(_, _))) => {
+        return writeln!(
+          writer,
+          "{} module could not be found",
+          colors::red("error:")
+        );
+      }
+      Err(err) => {
+        return writeln!(writer, "{} {:#}", colors::red("error:"), err);
+      }
+      Ok(None) => {
+        return writeln!(
+          writer,
+          "{} an internal error occurred",
+          colors::red("error:")
+        );
+      }
+    };
+
+    let total_modules_size = graph
+      .modules()
+      .map(|m| {
+        let size = match m {
+          Module::Esm(module) => module.size(),
+          Module::Json(module) => module.size(),
+          Module::Node(_) | Module::Npm(_) | Module::External(_) => 0,
+        };
+        size as f64
+      })
+      .sum::<f64>();
+    let total_npm_package_size = self
+      .npm_info
+      .package_sizes
+      .values()
+      .map(|s| *s as f64)
+      .sum::<f64>();
+    let module_dep_count = graph.modules().count() - 1; // -1 for the root module
+    let (dep_count, total_size) = if self.npm_only {
+      (self.npm_info.packages.len(), total_npm_package_size)
+    } else if self.visible_modules.is_some()
+      || self.exclude_from_totals
+      || self.runtime_modules.is_some()
+    {
+      self.filtered_totals(&root_specifier)
+    } else if self.no_npm {
+      // the npm specifiers are shown as excluded, so they aren't counted
+      let npm_module_count = graph
+        .modules()
+        .filter(|module| matches!(module, Module::Npm(_)))
+        .count();
+      (module_dep_count - npm_module_count, total_modules_size)
+    } else {
+      (
+        module_dep_count + self.npm_info.packages.len()
+          - self.npm_info.resolved_ids.len(),
+        total_modules_size + total_npm_package_size,
+      )
+    };
+    if self.percent {
+      self.percent_totals =
+        Some((total_size, total_modules_size + total_npm_package_size));
+    }
+    if self.count_only {
+      return writeln!(writer, "{}", dep_count);
+    }
+    if self.size_only {
+      return writeln!(writer, "{}", total_size as u64);
+    }
+
+    let maybe_cache_info = match root {
+      Module::Esm(module) => module.maybe_cache_info.as_ref(),
+      Module::Json(module) => module.maybe_cache_info.as_ref(),
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+    };
+    if let Some(cache_info) = maybe_cache_info {
+      if let Some(local) = &cache_info.local {
+        writeln!(
+          writer,
+          "{} {}",
+          colors::bold("local:"),
+          local.to_string_lossy()
+        )?;
+      }
+      if let Some(emit) = &cache_info.emit {
+        writeln!(
+          writer,
+          "{} {}",
+          colors::bold("emit:"),
+          emit.to_string_lossy()
+        )?;
+      }
+      if let Some(map) = &cache_info.map {
+        writeln!(writer, "{} {}", colors::bold("map:"), map.to_string_lossy())?;
+      }
+    }
+    if let Some(module) = root.esm() {
+      writeln!(writer, "{} {}", colors::bold("type:"), module.media_type)?;
+    }
+    let node_builtin_count = graph
+      .modules()
+      .filter(|module| matches!(module, Module::Node(_)))
+      .count();
+    // the breakdown only adds up when all the dependencies are counted
+    let is_partial_count = self.npm_only
+      || self.visible_modules.is_some()
+      || self.exclude_from_totals
+      || self.runtime_modules.is_some();
+    if is_partial_count {
+      writeln!(
+        writer,
+        "{} {} unique",
+        colors::bold("dependencies:"),
+        dep_count,
+      )?;
+    } else {
+      let (local_count, remote_count) = count_local_and_remote_modules(graph);
+      writeln!(
+        writer,
+        "{} {} unique ({} local, {} remote, {} npm)",
+        colors::bold("dependencies:"),
+        dep_count,
+        local_count,
+        remote_count,
+        dep_count - local_count - remote_count - node_builtin_count,
+      )?;
+    }
+    if node_builtin_count > 0 {
+      writeln!(
+        writer,
+        "{} {}",
+        colors::bold("node builtins:"),
+        node_builtin_count,
+      )?;
+    }
+    if !self.npm_info.packages.is_empty() {
+      let hosts = find_npm_registry_hosts(self.npm_info.packages.values());
+      if hosts.len() > 1 {
+        // packages coming from an unexpected registry are worth a look
+        writeln!(
+          writer,
+          "{} {}",
+          colors::bold("npm registries:"),
+          colors::yellow(format!(
+            "{} ({})",
+            hosts.len(),
+            hosts.into_iter().collect::<Vec<_>>().join(", ")
+          )),
+        )?;
+      } else {
+        writeln!(
+          writer,
+          "{} {}",
+          colors::bold("npm registries:"),
+          hosts.len(),
+        )?;
+      }
+    }
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("size:"),
+      display::human_size(total_size),
+    )?;
+    if let Some((excluded_count, excluded_size)) = self.runtime_excluded {
+      writeln!(
+        writer,
+        "{} {} {} ({}) only used for type checking",
+        colors::bold("excluded:"),
+        excluded_count,
+        if excluded_count == 1 {
+          "module"
+        } else {
+          "modules"
+        },
+        display::human_size(excluded_size as f64),
+      )?;
+    }
+    if self.split_sizes {
+      let sizes = SplitSizes::compute(graph, &self.npm_info);
+      for (label, size) in [
+        ("root:", sizes.root),
+        ("dependencies:", sizes.deps),
+        ("npm:", sizes.npm),
+      ] {
+        writeln!(
+          writer,
+          "  {} {}",
+          colors::bold(label),
+          display::human_size(size as f64)
+        )?;
+      }
+    }
+    if self.compressed {
+      let total_compressed_size = graph
+        .modules()
+        .filter_map(|module| self.compressed_size(module))
+        .sum::<u64>();
+      writeln!(
+        writer,
+        "{} {}",
+        colors::bold("compressed size:"),
+        display::human_size(total_compressed_size as f64),
+      )?;
+    }
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("lines:"),
+      count_source_lines(graph),
+    )?;
+    let checksums = self.checksums.insert(ModuleChecksums::compute(graph));
+    let module_count = checksums.module_count();
+    writeln!(
+      writer,
+      "{} {} of {} {}",
+      colors::bold("unique content:"),
+      checksums.unique_count(),
+      module_count,
+      if module_count == 1 {
+        "module"
+      } else {
+        "modules"
+      },
+    )?;
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("max depth:"),
+      find_graph_max_depth(graph),
+    )?;
+    let (runtime_count, type_only_count) =
+      count_runtime_and_type_only_deps(graph);
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("runtime deps:"),
+      runtime_count
+    )?;
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("type-only deps:"),
+      type_only_count
+    )?;
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("emitted modules:"),
+      graph.modules().filter(|m| module_needs_emit(m)).count()
+    )?;
+    writeln!(
+      writer,
+      "{} {}",
+      colors::bold("dynamic imports:"),
+      count_dynamic_imports(graph)
+    )?;
+    if let Some(used) = &self.used_import_map_entries {
+      writeln!(
+        writer,
+        "{} {}",
+        colors::bold("import map:"),
+        used.import_map_specifier,
+      )?;
+      writeln!(
+        writer,
+        "{} {} of {}",
+        colors::bold("used entries:"),
+        used.used_count(),
+        used.total_count,
+      )?;
+      for (key, value) in &used.imports {
+        writeln!(writer, "  {} -> {}", key, value)?;
+      }
+      for (scope, imports) in &used.scopes {
+        for (key, value) in imports {
+          writeln!(
+            writer,
+            "  {} -> {} {}",
+            key,
+            value,
+            colors::gray(format!("(scope: {scope})"))
+          )?;
+        }
+      }
+    }
+    if self.summary {
+      return Ok(());
+    }
+    writeln!(writer)?;
+    let insecure_imports = find_insecure_imports(graph);
+    if !insecure_imports.is_empty() {
+      writeln!(
+        writer,
+        "{} the following modules are imported over plain http, which allows them to be tampered with:",
+        colors::red_bold("warning:")
+      )?;
+      for specifier in insecure_imports {
+        writeln!(writer, "  {}", colors::red(specifier))?;
+      }
+      writeln!(writer)?;
+    }
+    let mut install_scripts = self
+      .npm_info
+      .package_jsons
+      .iter()
+      .filter(|(_, package_json)| !package_json.install_scripts.is_empty())
+      .map(|(id, package_json)| {
+        (id.as_serialized(), package_json.install_scripts.join(", "))
+      })
+      .collect::<Vec<_>>();
+    if !install_scripts.is_empty() {
+      install_scripts.sort();
+      writeln!(
+        writer,
+        "{} the following npm packages have install scripts, which run arbitrary code when they're installed:",
+        colors::red_bold("warning:")
+      )?;
+      for (id, scripts) in install_scripts {
+        writeln!(writer, "  {}: {}", colors::red(id), scripts)?;
+      }
+      writeln!(writer)?;
+    }
+    let duplicates =
+      find_duplicate_npm_packages(self.npm_info.packages.values());
+    if !duplicates.is_empty() {
+      writeln!(
+        writer,
+        "{} multiple versions of the following npm packages were resolved:",
+        colors::yellow("warning:")
+      )?;
+      for (name, versions) in duplicates {
+        let versions = versions
+          .iter()
+          .map(|version| format!("{name}@{version}"))
+          .collect::<Vec<_>>();
+        writeln!(writer, "  {}", versions.join(", "))?;
+      }
+      writeln!(writer)?;
+    }
+    if !self.import_map_conflicts.is_empty() {
+      writeln!(
+        writer,
+        "{} the following npm packages resolve to different versions through the import map than when imported directly:",
+        colors::yellow("warning:")
+      )?;
+      for conflict in &self.import_map_conflicts {
+        writeln!(
+          writer,
+          "  {}: {} through the import map, {} directly",
+          conflict.name,
+          join_versions(&conflict.mapped_versions),
+          join_versions(&conflict.direct_versions),
+        )?;
+      }
+      writeln!(writer)?;
+    }
+    let root_node = if self.group_npm_by_root {
+      self.build_npm_root_groups(root)
+    } else if self.npm_only {
+      self.build_npm_only_info(root)
+    } else {
+      self.build_module_info(requested_specifier, root, false)
+    };
+    let direct_dependencies = self.roots_only.then(|| {
+      let direct_size = root_node
+        .children
+        .iter()
+        .filter_map(|child| self.pending_size(child))
+        .sum::<u64>();
+      (root_node.children.len(), direct_size)
+    });
+    let total_nodes = self.write_tree(root_node, writer)?;
+    if let Some((direct_count, direct_size)) = direct_dependencies {
+      writeln!(
+        writer,
+        "{} {} ({})",
+        colors::bold("direct dependencies:"),
+        direct_count,
+        display::human_size(direct_size as f64),
+      )?;
+    }
+    if let Some(max_nodes) = self.max_nodes {
+      if total_nodes > max_nodes {
+        writeln!(writer)?;
+        writeln!(
+          writer,
+          "{}",
+          colors::gray(format!(
+            "... output truncated at {max_nodes} nodes ({total_nodes} total)"
+          ))
+        )?;
+      }
+    }
+    if !self.cycles.is_empty() {
+      writeln!(writer)?;
+      writeln!(
+        writer,
+        "{} {}",
+        colors::bold("circular dependencies:"),
+        self.cycles.len()
+      )?;
+    }
+    let error_count = count_graph_errors(graph, self.no_npm);
+    if error_count > 0 {
+      writeln!(writer)?;
+      writeln!(
+        writer,
+        "{} {} {} could not be loaded",
+        colors::red("error:"),
+        error_count,
+        if error_count == 1 {
+          "module"
+        } else {
+          "modules"
+        }
+      )?;
+    }
+    if let Some(warn_size) = self.warn_size {
+      let large_modules = find_large_modules(graph, warn_size);
+      if !large_modules.is_empty() {
+        writeln!(writer)?;
+        writeln!(
+          writer,
+          "{} the following modules are larger than {}:",
+          colors::yellow("warning:"),
+          display::human_size(warn_size as f64)
+        )?;
+        for (specifier, size) in large_modules {
+          writeln!(
+            writer,
+            "  {} {}",
+            specifier,
+            maybe_size_to_text(Some(size))
+          )?;
+        }
+      }
+    }
+    let mut deprecations = self
+      .npm_info
+      .package_jsons
+      .iter()
+      .filter_map(|(id, package_json)| {
+        Some((id.as_serialized(), package_json.deprecation.as_ref()?))
+      })
+      .collect::<Vec<_>>();
+    if !deprecations.is_empty() {
+      deprecations.sort();
+      writeln!(writer)?;
+      writeln!(
+        writer,
+        "{} the following npm packages are deprecated:",
+        colors::yellow("warning:")
+      )?;
+      for (id, message) in deprecations {
+        writeln!(writer, "  {}: {}", id, message)?;
+      }
+    }
+    if let Some(abbreviations) = &self.abbreviations {
+      if !abbreviations.prefixes.is_empty() {
+        writeln!(writer)?;
+        writeln!(writer, "{}", colors::bold("abbreviations:"))?;
+        for (abbreviation, prefix) in &abbreviations.prefixes {
+          writeln!(writer, "  {} = {}", abbreviation, prefix)?;
+        }
+      }
+    }
+    if self.legend {
+      writeln!(writer)?;
+      write_tree_legend(self.connectors, writer)?;
+    }
+    Ok(())
+  }
+
+  /// Writes the tree while it's traversed, which returns the number of
+  /// nodes including the ones left out by `--max-nodes`.
+  fn write_tree<TWrite: Write>(
+    &mut self,
+    root: BuiltNode<'a>,
+    writer: &mut TWrite,
+  ) -> Result<usize, fmt::Error> {
+    let mut progress = TreeProgress {
+      max_nodes: self.max_nodes,
+      total_nodes: 0,
+    };
+    progress.add_node();
+    write_tree_root(&root.text, self.tree_width, writer)?;
+    self.write_tree_children(root, "", &mut progress, writer)?;
+    Ok(progress.total_nodes)
+  }
+
+  fn write_tree_children<TWrite: Write>(
+    &mut self,
+    node: BuiltNode<'a>,
+    prefix: &str,
+    progress: &mut TreeProgress,
+    writer: &mut TWrite,
+  ) -> fmt::Result {
+    let child_len = node.children.len();
+    let has_ancestor_key = node.maybe_ancestor_key.is_some();
+    self.depth += 1;
+    self.ancestors.extend(node.maybe_ancestor_key);
+    for (index, pending) in node.children.into_iter().enumerate() {
+      let child = self.build_node(pending);
+      let child_prefix = if progress.add_node() {
+        // the last node that fits is drawn as the end of the tree
+        let is_last = index + 1 == child_len || progress.is_full();
+        write_tree_child(
+          &child.text,
+          self.connectors,
+          self.tree_width,
+          prefix,
+          is_last,
+          !child.children.is_empty() && !progress.is_full(),
+          writer,
+        )?;
+        tree_child_prefix(self.connectors, prefix, is_last)
+      } else {
+        // the nodes past the limit are still built to count them
+        String::new()
+      };
+      self.write_tree_children(child, &child_prefix, progress, writer)?;
+    }
+    if has_ancestor_key {
+      self.ancestors.pop();
+    }
+    self.depth -= 1;
+    Ok(())
+  }
+
+  fn build_node(&mut self, pending: PendingNode<'a>) -> BuiltNode<'a> {
+    match pending {
+      PendingNode::Dependency {
+        resolution,
+        type_dep,
+        maybe_referrer,
+        is_dynamic,
+        is_mapped,
+      } => {
+        let mut node = self.build_resolved_info(resolution, type_dep);
+        let maybe_location_text = maybe_referrer
+          .and_then(|referrer| self.maybe_location_text(referrer, resolution));
+        if let Some(location_text) = maybe_location_text {
+          node.text = format!("{} {}", node.text, location_text);
+        }
+        if is_dynamic {
+          node.text = format!("{} {}", node.text, colors::cyan("(dynamic)"));
+        }
+        if is_mapped {
+          node.text = format!("{} {}", node.text, colors::gray("(mapped)"));
+        }
+        node
+      }
+      PendingNode::NpmPackage {
+        id,
+        is_peer,
+        maybe_suffix,
+      } => {
+        let mut node = self.build_npm_package_info(&id, is_peer);
+        if let Some(suffix) = maybe_suffix {
+          node.text = format!("{} {}", node.text, suffix);
+        }
+        node
+      }
+      PendingNode::Text(text) => BuiltNode::from_text(text),
+    }
+  }
+
+  fn pending_dep_nodes(
+    &self,
+    referrer: &'a ModuleSpecifier,
+    specifier: &str,
+    dep: &'a Dependency,
+  ) -> Vec<PendingNode<'a>> {
+    let is_mapped = is_mapped_dependency(self.import_map, referrer, specifier);
+    let mut children = Vec::with_capacity(2);
+    if self.is_resolution_shown(&dep.maybe_code) {
+      children.push(PendingNode::Dependency {
+        resolution: &dep.maybe_code,
+        type_dep: false,
+        maybe_referrer: Some(referrer),
+        is_dynamic: dep.is_dynamic,
+        is_mapped,
+      });
+    }
+    if self.runtime_modules.is_none()
+      && self.is_resolution_shown(&dep.maybe_type)
+    {
+      children.push(PendingNode::Dependency {
+        resolution: &dep.maybe_type,
+        type_dep: true,
+        maybe_referrer: Some(referrer),
+        is_dynamic: dep.is_dynamic,
+        is_mapped,
+      });
+    }
+    children
+  }
+
+  fn build_module_info(
+    &mut self,
+    requested_specifier: &ModuleSpecifier,
+    module: &'a Module,
+    type_dep: bool,
+  ) -> BuiltNode<'a> {
+    if self.no_npm && module.npm().is_some() {
+      return self.build_excluded_npm_info(module.specifier());
+    }
+    if let Module::Node(module) = module {
+      // builtins are provided by the runtime, so they never have children
+      return BuiltNode::from_text(
+        colors::gray(format!("node:{} (builtin)", module.module_name))
+          .to_string(),
+      );
+    }
+
+    enum PackageOrSpecifier {
+      Package(NpmResolutionPackage),
+      Specifier(ModuleSpecifier),
+    }
+
+    use PackageOrSpecifier::*;
+
+    let package_or_specifier = match module.npm() {
+      Some(npm) => match self.npm_info.resolve_package(&npm.nv_reference.nv) {
+        Some(package) => Package(package.clone()),
+        None => Specifier(module.specifier().clone()), // should never happen
+      },
+      None => Specifier(module.specifier().clone()),
+    };
+    let seen_key = match &package_or_specifier {
+      Package(package) => package.id.as_serialized(),
+      Specifier(specifier) => specifier.to_string(),
+    };
+    let was_seen = self.was_seen(&seen_key);
+    if !self.seen.contains_key(&seen_key) {
+      self
+        .seen
+        .insert(seen_key.clone(), self.ancestors.last().cloned());
+    }
+    let maybe_size = match &package_or_specifier {
+      Package(package) => self.npm_info.package_sizes.get(&package.id).copied(),
+      Specifier(_) => match module {
+        Module::Esm(module) => Some(module.size() as u64),
+        Module::Json(module) => Some(module.size() as u64),
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+      },
+    };
+    let display_specifier = self.display_specifier(module.specifier());
+    let header_text = if was_seen {
+      let specifier_str = if type_dep {
+        colors::italic_gray(&display_specifier).to_string()
+      } else {
+        colors::gray(&display_specifier).to_string()
+      };
+      let mut header_text =
+        format!("{} {}", specifier_str, self.seen_marker(&seen_key));
+      if let Package(package) = &package_or_specifier {
+        if let Some(collapsed_text) = self.maybe_collapsed_text(&package.id) {
+          header_text = format!("{} {}", header_text, collapsed_text);
+        }
+      }
+      header_text
+    } else {
+      let header_text = if type_dep {
+        colors::italic(&display_specifier).to_string()
+      } else {
+        display_specifier
+      };
+      let mut header_text = self.color_by_type(module, header_text);
+      match &package_or_specifier {
+        Package(package) => {
+          if let Some(license_text) = self.maybe_license_text(&package.id) {
+            header_text = format!("{} {}", header_text, license_text);
+          }
+        }
+        Specifier(specifier) => {
+          if let Some(jsr_package) = jsr_package_from_specifier(specifier) {
+            header_text = format!(
+              "{} {}",
+              header_text,
+              colors::gray(format!("(jsr:{jsr_package})"))
+            );
+          }
+        }
+      }
+      let is_npm = matches!(package_or_specifier, Package(_));
+      header_text =
+        format!("{} {}", header_text, self.size_text(maybe_size, is_npm));
+      if let Package(package) = &package_or_specifier {
+        if let Some(format_text) = self.maybe_module_format_text(&package.id) {
+          header_text = format!("{} {}", header_text, format_text);
+        }
+        if let Some(package_json) = self.npm_info.package_jsons.get(&package.id)
+        {
+          if package_json.deprecation.is_some() {
+            header_text =
+              format!("{} {}", header_text, colors::red("(deprecated)"));
+          }
+          if !package_json.install_scripts.is_empty() {
+            header_text = format!(
+              "{} {}",
+              header_text,
+              colors::red("(has install scripts)")
+            );
+          }
+        }
+      }
+      let dep_count = match &package_or_specifier {
+        Package(package) => package.dependencies.len(),
+        Specifier(_) => module.esm().map_or(0, |module| {
+          if self.runtime_modules.is_some() {
+            // only count the imports that load code
+            module
+              .dependencies
+              .values()
+              .filter(|dep| !dep.maybe_code.is_none())
+              .count()
+          } else {
+            module.dependencies.len()
+              + usize::from(module.maybe_types_dependency.is_some())
+          }
+        }),
+      };
+      if let Some(dep_count_text) = self.maybe_dep_count_text(dep_count) {
+        header_text = format!("{} {}", header_text, dep_count_text);
+      }
+      if let Package(package) = &package_or_specifier {
+        if let Some(url_text) = self.maybe_npm_url_text(&package.id) {
+          header_text = format!("{} {}", header_text, url_text);
+        }
+      }
+      if let Specifier(_) = &package_or_specifier {
+        let is_large = matches!(
+          (maybe_size, self.warn_size),
+          (Some(size), Some(warn_size)) if size > warn_size
+        );
+        if is_large {
+          header_text = format!("{} {}", header_text, colors::red("(large)"));
+        }
+      }
+      if let Some(subtree_sizes) = &self.subtree_sizes {
+        let subtree_size = match &package_or_specifier {
+          Package(package) => Some(self.npm_info.subtree_size(&package.id)),
+          Specifier(specifier) => subtree_sizes.get(specifier).copied(),
+        };
+        if let Some(subtree_size) = subtree_size {
+          header_text = format!(
+            "{} {}",
+            header_text,
+            colors::gray(format!(
+              "({} total)",
+              display::human_size(subtree_size as f64)
+            ))
+          );
+        }
+      }
+      if let Some(type_text) = self.maybe_module_type_text(module) {
+        header_text = format!("{} {}", header_text, type_text);
+      }
+      if let Some(checksum_text) = self.maybe_checksum_text(module) {
+        header_text = format!("{} {}", header_text, checksum_text);
+      }
+      if let Some(emit_text) = self.maybe_emit_text(module) {
+        header_text = format!("{} {}", header_text, emit_text);
+      }
+      match self.maybe_cache_state_text(module.specifier()) {
+        Some(cache_state_text) => {
+          format!("{} {}", header_text, cache_state_text)
+        }
+        None => header_text,
+      }
+    };
+
+    // npm specifiers are resolved to a package version, which isn't a redirect
+    let is_redirect =
+      module.npm().is_none() && requested_specifier != module.specifier();
+    let header_text = if is_redirect {
+      format!(
+        "{} {}",
+        colors::gray(format!(
+          "{} ->",
+          self.display_specifier(requested_specifier)
+        )),
+        header_text
+      )
+    } else {
+      header_text
+    };
+
+    let mut tree_node = BuiltNode::from_text(header_text);
+
+    if !was_seen {
+      if self.is_at_max_depth() {
+        let has_children = match &package_or_specifier {
+          Package(package) => !package.dependencies.is_empty(),
+          Specifier(_) => module.esm().map_or(false, |module| {
+            !module.dependencies.is_empty()
+              || (module.maybe_types_dependency.is_some()
+                && self.runtime_modules.is_none())
+          }),
+        };
+        // the deeper levels are left out on purpose with `--roots-only`
+        if has_children && !self.roots_only {
+          tree_node.text =
+            format!("{} {}", tree_node.text, colors::gray("..."));
+          // allow the module to be expanded if it appears at a shallower depth
+          self.seen.remove(&seen_key);
+        }
+        return tree_node;
+      }
+
+      match &package_or_specifier {
+        Package(package) => {
+          tree_node.children = self.pending_npm_deps(package);
+        }
+        Specifier(_) => {
+          if let Some(module) = module.esm() {
+            let types_dep = module
+              .maybe_types_dependency
+              .as_ref()
+              .filter(|_| self.runtime_modules.is_none())
+              .filter(|dep| self.is_resolution_shown(&dep.dependency));
+            if let Some(types_dep) = types_dep {
+              tree_node.children.push(PendingNode::Dependency {
+                resolution: &types_dep.dependency,
+                type_dep: true,
+                maybe_referrer: None,
+                is_dynamic: false,
+                is_mapped: false,
+              });
+            }
+            // the dependencies are kept in the order they appear in the
+            // source, so which import expands a shared module is stable
+            for (specifier, dep) in &module.dependencies {
+              tree_node.children.extend(self.pending_dep_nodes(
+                &module.specifier,
+                specifier,
+                dep,
+              ));
+            }
+          }
+        }
+      }
+      self.sort_children(&mut tree_node.children);
+      tree_node.maybe_ancestor_key = Some(seen_key);
+    }
+    tree_node
+  }
+
+  fn sort_children(&self, children: &mut [PendingNode]) {
+    match self.sort {
+      // modules and packages are already built in alphabetical order
+      InfoSortOrder::Name => {}
+      InfoSortOrder::Size => {
+        children.sort_by(|a, b| {
+          match (self.pending_size(a), self.pending_size(b)) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+          }
+        });
+      }
+    }
+  }
+
+  /// Gets the size of the module or package a pending node is built for.
+  fn pending_size(&self, pending: &PendingNode) -> Option<u64> {
+    match pending {
+      PendingNode::Dependency { resolution, .. } => {
+        let specifier = self.graph.resolve(&resolution.ok()?.specifier);
+        match self.graph.get(&specifier)? {
+          Module::Esm(module) => Some(module.size() as u64),
+          Module::Json(module) => Some(module.size() as u64),
+          // the npm specifiers are shown as excluded
+          Module::Npm(_) if self.no_npm => None,
+          Module::Npm(module) => {
+            let package =
+              self.npm_info.resolve_package(&module.nv_reference.nv)?;
+            self.npm_info.package_sizes.get(&package.id).copied()
+          }
+          Module::Node(_) | Module::External(_) => None,
+        }
+      }
+      PendingNode::NpmPackage { id, .. } => {
+        self.npm_info.package_sizes.get(id).copied()
+      }
+      PendingNode::Text(_) => None,
+    }
+  }
+
+  /// Whether a module or package was already displayed and shouldn't be
+  /// expanded again. With `--no-dedup`, only the ancestors are checked so
+  /// everything except cycles is expanded.
+  fn was_seen(&self, seen_key: &str) -> bool {
+    if self.no_dedup {
+      self.ancestors.iter().any(|key| key == seen_key)
+    } else {
+      self.seen.contains_key(seen_key)
+    }
+  }
+
+  /// Gets the marker for a module or package that was already displayed,
+  /// which is either a cycle back to one of its ancestors or a reference to
+  /// where it was shown elsewhere in the tree.
+  fn seen_marker(&mut self, seen_key: &str) -> String {
+    match self.ancestors.iter().position(|key| key == seen_key) {
+      Some(index) => {
+        let mut cycle = self.ancestors[index..].to_vec();
+        // normalize the cycle so it's only counted once regardless of
+        // which of its modules it was entered from
+        let min_index = cycle
+          .iter()
+          .enumerate()
+          .min_by(|a, b| a.1.cmp(b.1))
+          .map(|(index, _)| index)
+          .unwrap_or(0);
+        cycle.rotate_left(min_index);
+        self.cycles.insert(cycle);
+        colors::yellow("(circular)").to_string()
+      }
+      None => {
+        let marker = colors::gray("*").to_string();
+        match self.seen.get(seen_key) {
+          Some(Some(parent_key)) if self.verbose_dedup => {
+            let parent = match ModuleSpecifier::parse(parent_key) {
+              Ok(specifier) => self.display_specifier(&specifier),
+              // packages are keyed by their id
+              Err(_) => format!("npm:{parent_key}"),
+            };
+            format!(
+              "{} {}",
+              marker,
+              colors::gray(format!("(first via {parent})"))
+            )
+          }
+          _ => marker,
+        }
+      }
+    }
+  }
+
+  /// Gets the text describing how many transitive dependencies were hidden
+  /// for a package that was already displayed.
+  fn maybe_collapsed_text(&self, package_id: &NpmPackageId) -> Option<String> {
+    if !self.verbose_dedup {
+      return None;
+    }
+    let count = *self.npm_info.transitive_dep_counts.get(package_id)?;
+    if count == 0 {
+      return None;
+    }
+    Some(colors::gray(format!("(+{count} deps)")).to_string())
+  }
+
+  /// Gets the number of direct dependencies to show for non-leaf nodes with
+  /// `--show-dep-counts`.
+  fn maybe_dep_count_text(&self, count: usize) -> Option<String> {
+    if !self.show_dep_counts || count == 0 {
+      return None;
+    }
+    let text = if count == 1 {
+      "[1 dep]".to_string()
+    } else {
+      format!("[{count} deps]")
+    };
+    Some(colors::gray(text).to_string())
+  }
+
+  fn maybe_license_text(&self, package_id: &NpmPackageId) -> Option<String> {
+    if !self.show_licenses {
+      return None;
+    }
+    let license = self
+      .npm_info
+      .package_jsons
+      .get(package_id)?
+      .license
+      .as_ref()?;
+    Some(colors::gray(license).to_string())
+  }
+
+  /// Gets where the import statement of a direct dependency of the root is
+  /// located, as a 1-based line and column in the importing file.
+  fn maybe_location_text(
+    &self,
+    referrer: &ModuleSpecifier,
+    resolution: &Resolution,
+  ) -> Option<String> {
+    // the dependencies of the roots are built at a depth of one
+    if !self.show_locations || self.depth != 1 {
+      return None;
+    }
+    let range = &resolution.ok()?.range;
+    let file_name = referrer
+      .path_segments()
+      .and_then(|mut segments| segments.next_back())
+      .unwrap_or(referrer.as_str());
+    Some(
+      colors::gray(format!(
+        "(imported at {}:{}:{})",
+        file_name,
+        range.start.line + 1,
+        range.start.character + 1
+      ))
+      .to_string(),
+    )
+  }
+
+  /// Gets the text for the size of a module or npm package, which includes
+  /// its share of the total size with `--percent`.
+  fn size_text(&self, maybe_size: Option<u64>, is_npm: bool) -> String {
+    let (Some(size), Some((total_size, total_npm_size))) =
+      (maybe_size, self.percent_totals)
+    else {
+      return maybe_size_to_text(maybe_size);
+    };
+    let total_size = if is_npm { total_npm_size } else { total_size };
+    colors::gray(format!(
+      "({}, {:.0}%)",
+      display::human_size(size as f64),
+      size_percentage(size as f64, total_size)
+    ))
+    .to_string()
+  }
+
+  fn maybe_module_format_text(
+    &self,
+    package_id: &NpmPackageId,
+  ) -> Option<String> {
+    let module_format =
+      self.npm_info.package_jsons.get(package_id)?.module_format?;
+    Some(colors::gray(format!("[{}]", module_format.as_str())).to_string())
+  }
+
+  fn maybe_npm_url_text(&self, package_id: &NpmPackageId) -> Option<String> {
+    if !self.show_npm_urls {
+      return None;
+    }
+    let package = self.npm_info.packages.get(package_id)?;
+    let tarball = npm_package_tarball(package)?;
+    Some(colors::gray(tarball).to_string())
+  }
+
+  fn is_visible(&self, specifier: &ModuleSpecifier) -> bool {
+    match &self.visible_modules {
+      Some(visible_modules) => visible_modules.contains(specifier),
+      None => true,
+    }
+  }
+
+  fn is_excluded(&self, specifier: &ModuleSpecifier) -> bool {
+    match &self.exclude {
+      Some(exclude) => exclude.matches_str(specifier.as_str()),
+      None => false,
+    }
+  }
+
+  /// Gets if the module counts towards the dependency count and total size.
+  fn is_counted(&self, specifier: &ModuleSpecifier) -> bool {
+    let is_runtime = match &self.runtime_modules {
+      Some(runtime_modules) => runtime_modules.contains(specifier),
+      None => true,
+    };
+    self.is_visible(specifier)
+      && is_runtime
+      && !(self.exclude_from_totals && self.is_excluded(specifier))
+  }
+
+  /// Gets the dependency count and total size of the modules left after
+  /// applying the filter and exclusions, including the npm packages they use.
+  fn filtered_totals(&self, root_specifier: &ModuleSpecifier) -> (usize, f64) {
+    let mut module_dep_count = 0;
+    let mut total_size = 0;
+    let mut package_ids = HashSet::new();
+    for module in self.graph.modules() {
+      let specifier = module.specifier();
+      if !self.is_counted(specifier) {
+        continue;
+      }
+      if let Some(package_id) = module
+        .npm()
+        .and_then(|npm| self.npm_info.resolved_ids.get(&npm.nv_reference.nv))
+      {
+        package_ids.extend(self.npm_info.collect_packages(package_id));
+        continue;
+      }
+      if specifier != root_specifier {
+        module_dep_count += 1;
+      }
+      total_size += match module {
+        Module::Esm(module) => module.size() as u64,
+        Module::Json(module) => module.size() as u64,
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => 0,
+      };
+    }
+    total_size += package_ids
+      .iter()
+      .filter_map(|id| self.npm_info.package_sizes.get(*id))
+      .sum::<u64>();
+    (module_dep_count + package_ids.len(), total_size as f64)
+  }
+
+  fn compressed_size(&mut self, module: &Module) -> Option<u64> {
+    if let Some(size) = self.compressed_sizes.get(module.specifier()) {
+      return Some(*size);
+    }
+    let size = compressed_module_size(module)?;
+    self
+      .compressed_sizes
+      .insert(module.specifier().clone(), size);
+    Some(size)
+  }
+
+  fn maybe_cache_state_text(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<String> {
+    let file_fetcher = self.file_fetcher?;
+    if !matches!(specifier.scheme(), "http" | "https") {
+      return None;
+    }
+    let cache_state = cache_state_text(file_fetcher, specifier);
+    Some(colors::gray(format!("({cache_state})")).to_string())
+  }
+
+  fn maybe_checksum_text(&self, module: &Module) -> Option<String> {
+    if !self.show_checksums {
+      return None;
+    }
+    // a short prefix is enough to compare against the lockfile by eye
+    let checksum = self.checksums.as_ref()?.remote(module)?;
+    Some(colors::gray(&checksum[..12]).to_string())
+  }
+
+  fn maybe_emit_text(&self, module: &Module) -> Option<String> {
+    if !self.show_emit || !module_needs_emit(module) {
+      return None;
+    }
+    let needs_emit_text = colors::gray("(needs emit)");
+    let maybe_emit = module
+      .esm()?
+      .maybe_cache_info
+      .as_ref()
+      .and_then(|cache_info| cache_info.emit.as_ref());
+    match maybe_emit {
+      Some(emit) => Some(format!(
+        "{} {}",
+        needs_emit_text,
+        colors::gray(format!("(emit: {})", emit.to_string_lossy()))
+      )),
+      None => Some(needs_emit_text.to_string()),
+    }
+  }
+
+  /// Colors the specifier of a module by its type with `--color-by-type`.
+  fn color_by_type(&self, module: &Module, text: String) -> String {
+    if !self.color_by_type {
+      return text;
+    }
+    match module {
+      Module::Esm(module) => match module.media_type {
+        MediaType::TypeScript
+        | MediaType::Mts
+        | MediaType::Cts
+        | MediaType::Dts
+        | MediaType::Dmts
+        | MediaType::Dcts
+        | MediaType::Tsx => colors::blue(text).to_string(),
+        MediaType::JavaScript
+        | MediaType::Mjs
+        | MediaType::Cjs
+        | MediaType::Jsx => colors::yellow(text).to_string(),
+        _ => text,
+      },
+      Module::Json(_) => colors::green(text).to_string(),
+      Module::Npm(_) => colors::magenta(text).to_string(),
+      Module::Node(_) | Module::External(_) => text,
+    }
+  }
+
+  fn maybe_module_type_text(&self, module: &Module) -> Option<String> {
+    match module {
+      Module::Esm(module) => {
+        self.maybe_type_text(&module.media_type.to_string())
+      }
+      Module::Json(module) => {
+        self.maybe_type_text(&module.media_type.to_string())
+      }
+      Module::Npm(_) => self.maybe_type_text("npm"),
+      Module::Node(_) => self.maybe_type_text("node"),
+      Module::External(_) => None,
+    }
+  }
+
+  fn maybe_type_text(&self, type_name: &str) -> Option<String> {
+    if !self.show_types {
+      return None;
+    }
+    Some(colors::gray(format!("[{type_name}]")).to_string())
+  }
+
+  fn is_at_max_depth(&self) -> bool {
+    matches!(self.max_depth, Some(max_depth) if self.depth >= max_depth)
+  }
+
+  /// Builds a tree of only the npm packages referenced by the graph, with
+  /// the packages the graph references directly as the root's children.
+  fn build_npm_only_info(&self, root: &Module) -> BuiltNode<'a> {
+    let mut tree_node = BuiltNode::from_text(root.specifier().to_string());
+    let root_ids = self
+      .npm_info
+      .resolved_ids
+      .values()
+      .cloned()
+      .collect::<HashSet<_>>()
+      .into_iter()
+      .collect::<Vec<_>>();
+    tree_node.children = self.pending_npm_packages(root_ids, &HashSet::new());
+    tree_node
+  }
+
+  /// Builds a subtree for each directly imported npm package, where the
+  /// packages shared between them are attributed to the first one.
+  fn build_npm_root_groups(&self, root: &Module) -> BuiltNode<'a> {
+    let mut tree_node = BuiltNode::from_text(root.specifier().to_string());
+    let mut root_ids = self
+      .npm_info
+      .resolved_ids
+      .values()
+      .cloned()
+      .collect::<HashSet<_>>()
+      .into_iter()
+      .collect::<Vec<_>>();
+    root_ids.sort();
+    let mut owned_ids = HashSet::new();
+    for root_id in root_ids {
+      let mut owned_count = 0;
+      let mut owned_size = 0;
+      let mut pending = vec![root_id.clone()];
+      while let Some(id) = pending.pop() {
+        if !owned_ids.insert(id.clone()) {
+          continue;
+        }
+        owned_count += 1;
+        owned_size +=
+          self.npm_info.package_sizes.get(&id).copied().unwrap_or(0);
+        if let Some(package) = self.npm_info.packages.get(&id) {
+          pending.extend(package.dependencies.values().cloned());
+        }
+      }
+      let owned_text = colors::gray(format!(
+        "(owns {} {}, {})",
+        owned_count,
+        if owned_count == 1 {
+          "package"
+        } else {
+          "packages"
+        },
+        display::human_size(owned_size as f64)
+      ));
+      tree_node.children.push(PendingNode::NpmPackage {
+        id: root_id,
+        is_peer: false,
+        maybe_suffix: Some(owned_text.to_string()),
+      });
+    }
+    self.sort_children(&mut tree_node.children);
+    tree_node
+  }
+
+  fn pending_npm_deps(
+    &self,
+    package: &NpmResolutionPackage,
+  ) -> Vec<PendingNode<'a>> {
+    let peer_dependencies = self
+      .npm_info
+      .package_jsons
+      .get(&package.id)
+      .map(|package_json| package_json.peer_dependencies.as_slice())
+      .unwrap_or_default();
+    // the resolved peers are part of the dependencies of the package
+    let peer_ids = peer_dependencies
+      .iter()
+      .filter_map(|peer| package.dependencies.get(&peer.name).cloned())
+      .collect::<HashSet<_>>();
+    let mut children = self.pending_npm_packages(
+      package.dependencies.values().cloned().collect(),
+      &peer_ids,
+    );
+    // the peers that weren't resolved have no package to show, so they're
+    // listed after the dependencies with the version they asked for
+    for peer in peer_dependencies {
+      if package.dependencies.contains_key(&peer.name) {
+        continue;
+      }
+      let marker_text = if peer.optional {
+        colors::gray("(optional peer, not installed)").to_string()
+      } else {
+        colors::red("(unmet peer)").to_string()
+      };
+      children.push(PendingNode::Text(format!(
+        "npm:{}@{} {}",
+        peer.name, peer.version_req, marker_text
+      )));
+    }
+    children
+  }
+
+  fn pending_npm_packages(
+    &self,
+    mut deps: Vec<NpmPackageId>,
+    peer_ids: &HashSet<NpmPackageId>,
+  ) -> Vec<PendingNode<'a>> {
+    deps.sort();
+    let mut children = deps
+      .into_iter()
+      .map(|id| PendingNode::NpmPackage {
+        is_peer: peer_ids.contains(&id),
+        id,
+        maybe_suffix: None,
+      })
+      .collect::<Vec<_>>();
+    self.sort_children(&mut children);
+    children
+  }
+
+  fn build_npm_package_info(
+    &mut self,
+    dep_id: &NpmPackageId,
+    is_peer: bool,
+  ) -> BuiltNode<'a> {
+    let maybe_size = self.npm_info.package_sizes.get(dep_id).cloned();
+    let size_str = self.size_text(maybe_size, true);
+    let specifier_text = format!("npm:{}", dep_id.as_serialized());
+    let specifier_text = if self.color_by_type {
+      colors::magenta(specifier_text).to_string()
+    } else {
+      specifier_text
+    };
+    let mut child =
+      BuiltNode::from_text(match self.maybe_license_text(dep_id) {
+        Some(license_text) => {
+          format!("{} {} {}", specifier_text, license_text, size_str)
+        }
+        None => format!("{} {}", specifier_text, size_str),
+      });
+    if is_peer {
+      child.text = format!("{} {}", child.text, colors::cyan("(peer)"));
+    }
+    if let Some(format_text) = self.maybe_module_format_text(dep_id) {
+      child.text = format!("{} {}", child.text, format_text);
+    }
+    if let Some(package_json) = self.npm_info.package_jsons.get(dep_id) {
+      if package_json.deprecation.is_some() {
+        child.text = format!("{} {}", child.text, colors::red("(deprecated)"));
+      }
+      if !package_json.install_scripts.is_empty() {
+        child.text =
+          format!("{} {}", child.text, colors::red("(has install scripts)"));
+      }
+    }
+    if let Some(package) = self.npm_info.packages.get(dep_id) {
+      let dep_count = package.dependencies.len();
+      if let Some(dep_count_text) = self.maybe_dep_count_text(dep_count) {
+        child.text = format!("{} {}", child.text, dep_count_text);
+      }
+    }
+    if let Some(url_text) = self.maybe_npm_url_text(dep_id) {
+      child.text = format!("{} {}", child.text, url_text);
+    }
+    if let Some(type_text) = self.maybe_type_text("npm") {
+      child.text = format!("{} {}", child.text, type_text);
+    }
+    if let Some(package) = self.npm_info.packages.get(dep_id) {
+      // the unmet peers are shown as children too
+      if !package.dependencies.is_empty()
+        || self
+          .npm_info
+          .package_jsons
+          .get(dep_id)
+          .map_or(false, |package_json| {
+            !package_json.peer_dependencies.is_empty()
+          })
+      {
+        let seen_key = package.id.as_serialized();
+        if self.was_seen(&seen_key) {
+          child.text =
+            format!("{} {}", child.text, self.seen_marker(&seen_key));
+          if let Some(collapsed_text) = self.maybe_collapsed_text(dep_id) {
+            child.text = format!("{} {}", child.text, collapsed_text);
+          }
+        } else if self.is_at_max_depth() {
+          child.text = format!("{} {}", child.text, colors::gray("..."));
+        } else {
+          if !self.seen.contains_key(&seen_key) {
+            self
+              .seen
+              .insert(seen_key.clone(), self.ancestors.last().cloned());
+          }
+          child.children = self.pending_npm_deps(package);
+          child.maybe_ancestor_key = Some(seen_key);
+        }
+      }
+    }
+    child
+  }
+
+  /// Gets the text to show for a specifier in the tree.
+  fn display_specifier(&self, specifier: &ModuleSpecifier) -> String {
+    if let Some(relative_to) = &self.relative_to {
+      if specifier.scheme() == "file" {
+        if let Some(text) = relative_specifier(relative_to, specifier) {
+          return text;
+        }
+      }
+    }
+    match &self.abbreviations {
+      Some(abbreviations) => abbreviations.abbreviate(specifier.as_str()),
+      None => specifier.to_string(),
+    }
+  }
+
+  fn build_excluded_npm_info(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> BuiltNode<'a> {
+    BuiltNode::from_text(format!(
+      "{} {}",
+      specifier,
+      colors::gray("(excluded)")
+    ))
+  }
+
+  fn build_error_info(
+    &mut self,
+    err: &ModuleGraphError,
+    specifier: &ModuleSpecifier,
+  ) -> BuiltNode<'a> {
+    if self.no_npm && specifier.scheme() == "npm" {
+      // npm specifiers fail to load when npm resolution is disabled
+      return self.build_excluded_npm_info(specifier);
+    }
+    self
+      .seen
+      .insert(specifier.to_string(), self.ancestors.last().cloned());
+    match err {
+      ModuleGraphError::ModuleError(err) => match err {
+        ModuleError::InvalidTypeAssertion { .. } => {
+          self.build_error_msg(specifier, "(invalid import assertion)")
+        }
+        ModuleError::LoadingErr(_, _, _) => {
+          self.build_error_msg(specifier, "(loading error)")
+        }
+        ModuleError::ParseErr(_, _) => {
+          self.build_error_msg(specifier, "(parsing error)")
+        }
+        ModuleError::UnsupportedImportAssertionType { .. } => {
+          self.build_error_msg(specifier, "(unsupported import assertion)")
+        }
+        ModuleError::UnsupportedMediaType { .. } => {
+          self.build_error_msg(specifier, "(unsupported)")
+        }
+        ModuleError::Missing(_, _) | ModuleError::MissingDynamic(_, _) => {
+          self.build_error_msg(specifier, "(missing)")
+        }
+      },
+      ModuleGraphError::ResolutionError(_) => {
+        self.build_error_msg(specifier, "(resolution error)")
+      }
+    }
+  }
+
+  fn build_error_msg(
+    &self,
+    specifier: &ModuleSpecifier,
+    error_msg: &str,
+  ) -> BuiltNode<'a> {
+    BuiltNode::from_text(format!(
+      "{} {}",
+      colors::red(self.display_specifier(specifier)),
+      colors::red_bold(error_msg)
+    ))
+  }
+
+  /// Whether a dependency is shown in the tree, which is known before it's
+  /// built so the connectors of its siblings can be drawn.
+  fn is_resolution_shown(&self, resolution: &Resolution) -> bool {
+    match resolution {
+      Resolution::Ok(resolved) => {
+        let resolved_specifier = self.graph.resolve(&resolved.specifier);
+        self.is_visible(&resolved_specifier)
+          && !self.is_excluded(&resolved_specifier)
+      }
+      // resolution errors never match the filter
+      Resolution::Err(_) => self.visible_modules.is_none(),
+      Resolution::None => false,
+    }
+  }
+
+  fn build_resolved_info(
+    &mut self,
+    resolution: &'a Resolution,
+    type_dep: bool,
+  ) -> BuiltNode<'a> {
+    let graph = self.graph;
+    match resolution {
+      Resolution::Ok(resolved) => {
+        let specifier = &resolved.specifier;
+        let resolved_specifier = graph.resolve(specifier);
+        match graph.try_get(&resolved_specifier) {
+          Ok(Some(module)) => {
+            self.build_module_info(specifier, module, type_dep)
+          }
+          Err(err) => self.build_error_info(err, &resolved_specifier),
+          Ok(None) => BuiltNode::from_text(format!(
+            "{} {}",
+            colors::red(self.display_specifier(specifier)),
+            colors::red_bold("(missing)")
+          )),
+        }
+      }
+      Resolution::Err(err) => BuiltNode::from_text(format!(
+        "{} {}",
+        colors::italic(err.to_string()),
+        colors::red_bold("(resolve error)")
+      )),
+      Resolution::None => {
+        unreachable!("dependencies without a resolution aren't shown")
+      }
+    }
+  }
+}
+
+fn maybe_size_to_text(maybe_size: Option<u64>) -> String {
+  colors::gray(maybe_size_to_plain_text(maybe_size)).to_string()
+}
+
+fn maybe_size_to_plain_text(maybe_size: Option<u64>) -> String {
+  format!(
+    "({})",
+    match maybe_size {
+      Some(size) => display::human_size(size as f64),
+      None => "unknown".to_string(),
+    }
+  )
+}
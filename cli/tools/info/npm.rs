@@ -0,0 +1,722 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_npm::resolution::NpmResolutionSnapshot;
+use deno_npm::NpmPackageId;
+use deno_npm::NpmResolutionPackage;
+use deno_runtime::colors;
+use deno_semver::npm::NpmPackageNv;
+use deno_semver::npm::NpmPackageNvReference;
+use deno_semver::npm::NpmPackageReqReference;
+use deno_semver::Version;
+use lazy_regex::lazy_regex;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::display;
+use crate::npm::CliNpmResolver;
+
+pub fn add_npm_packages_to_json(
+  json: &mut serde_json::Value,
+  npm_resolver: &CliNpmResolver,
+) {
+  // ideally deno_graph could handle this, but for now we just modify the json here
+  let snapshot = npm_resolver.snapshot();
+  let json = json.as_object_mut().unwrap();
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  if let Some(modules) = modules {
+    if modules.len() == 1
+      && modules[0].get("kind").and_then(|k| k.as_str()) == Some("npm")
+    {
+      // If there is only one module and it's "external", then that means
+      // someone provided an npm specifier as a cli argument. In this case,
+      // we want to show which npm package the cli argument resolved to.
+      let module = &mut modules[0];
+      let maybe_package = module
+        .get("specifier")
+        .and_then(|k| k.as_str())
+        .and_then(|specifier| NpmPackageNvReference::from_str(specifier).ok())
+        .and_then(|package_ref| {
+          snapshot
+            .resolve_package_from_deno_module(&package_ref.nv)
+            .ok()
+        });
+      if let Some(pkg) = maybe_package {
+        if let Some(module) = module.as_object_mut() {
+          module
+            .insert("npmPackage".to_string(), pkg.id.as_serialized().into());
+        }
+      }
+    } else {
+      // Filter out npm package references from the modules and instead
+      // have them only listed as dependencies. This is done because various
+      // npm specifiers modules in the graph are really just unresolved
+      // references. So there could be listed multiple npm specifiers
+      // that would resolve to a single npm package.
+      for i in (0..modules.len()).rev() {
+        if matches!(
+          modules[i].get("kind").and_then(|k| k.as_str()),
+          Some("npm") | Some("external")
+        ) {
+          modules.remove(i);
+        }
+      }
+    }
+
+    for module in modules.iter_mut() {
+      let dependencies = module
+        .get_mut("dependencies")
+        .and_then(|d| d.as_array_mut());
+      if let Some(dependencies) = dependencies {
+        for dep in dependencies.iter_mut() {
+          if let serde_json::Value::Object(dep) = dep {
+            let specifier = dep.get("specifier").and_then(|s| s.as_str());
+            if let Some(specifier) = specifier {
+              if let Ok(npm_ref) = NpmPackageReqReference::from_str(specifier) {
+                if let Ok(pkg) = snapshot.resolve_pkg_from_pkg_req(&npm_ref.req)
+                {
+                  dep.insert(
+                    "npmPackage".to_string(),
+                    pkg.id.as_serialized().into(),
+                  );
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+
+  let mut sorted_packages =
+    snapshot.all_packages_for_every_system().collect::<Vec<_>>();
+  sorted_packages.sort_by(|a, b| a.id.cmp(&b.id));
+  let duplicates = find_duplicate_npm_packages(sorted_packages.iter().copied())
+    .into_iter()
+    .map(|(name, versions)| {
+      let versions = versions
+        .into_iter()
+        .map(|version| serde_json::Value::String(version.to_string()))
+        .collect::<Vec<_>>();
+      (name, serde_json::Value::Array(versions))
+    })
+    .collect::<serde_json::Map<_, _>>();
+  let mut json_packages = serde_json::Map::with_capacity(sorted_packages.len());
+  for pkg in sorted_packages {
+    let mut kv = serde_json::Map::new();
+    kv.insert("name".to_string(), pkg.id.nv.name.to_string().into());
+    kv.insert("version".to_string(), pkg.id.nv.version.to_string().into());
+    let mut deps = pkg.dependencies.values().collect::<Vec<_>>();
+    deps.sort();
+    let deps = deps
+      .into_iter()
+      .map(|id| serde_json::Value::String(id.as_serialized()))
+      .collect::<Vec<_>>();
+    kv.insert("dependencies".to_string(), deps.into());
+    let package_json = NpmPackageJson::read(npm_resolver, &pkg.id);
+    kv.insert("license".to_string(), package_json.license.into());
+    kv.insert("tarball".to_string(), npm_package_tarball(pkg).into());
+    kv.insert("sideEffects".to_string(), package_json.side_effects);
+    kv.insert(
+      "moduleFormat".to_string(),
+      package_json
+        .module_format
+        .map(|module_format| module_format.as_str())
+        .into(),
+    );
+    if let Some(message) = package_json.deprecation {
+      kv.insert("deprecated".to_string(), message.into());
+    }
+    if !package_json.install_scripts.is_empty() {
+      kv.insert("hasInstallScripts".to_string(), true.into());
+    }
+    if !package_json.peer_dependencies.is_empty() {
+      let peer_dependencies = package_json
+        .peer_dependencies
+        .into_iter()
+        .map(|peer| {
+          json!({
+            "name": peer.name,
+            "versionReq": peer.version_req,
+            "optional": peer.optional,
+            "resolved": pkg
+              .dependencies
+              .get(&peer.name)
+              .map(|id| id.as_serialized()),
+          })
+        })
+        .collect::<Vec<_>>();
+      kv.insert("peerDependencies".to_string(), peer_dependencies.into());
+    }
+
+    json_packages.insert(pkg.id.as_serialized(), kv.into());
+  }
+
+  json.insert("npmPackages".to_string(), json_packages.into());
+  json.insert("duplicateNpmPackages".to_string(), duplicates.into());
+}
+
+/// The fields of the package.json of an npm package that deno info shows,
+/// which is read and parsed once per package.
+#[derive(Debug, Default)]
+pub struct NpmPackageJson {
+  pub license: Option<String>,
+  pub side_effects: serde_json::Value,
+  pub module_format: Option<NpmModuleFormat>,
+  /// Deprecation message of a deprecated package.
+  pub deprecation: Option<String>,
+  pub install_scripts: Vec<&'static str>,
+  pub peer_dependencies: Vec<NpmPeerDependency>,
+}
+
+impl NpmPackageJson {
+  /// Reads the package.json of an npm package, leaving the fields empty when
+  /// it can't be read.
+  pub fn read(
+    npm_resolver: &CliNpmResolver,
+    package_id: &NpmPackageId,
+  ) -> Self {
+    let Ok(package_json) = npm_resolver.package_json_value(package_id) else {
+      return Self::default();
+    };
+    Self {
+      license: resolve_npm_package_license(&package_json),
+      side_effects: resolve_npm_package_side_effects(&package_json),
+      module_format: Some(resolve_npm_package_module_format(&package_json)),
+      deprecation: resolve_npm_package_deprecation(&package_json),
+      install_scripts: resolve_npm_package_install_scripts(&package_json),
+      peer_dependencies: resolve_npm_package_peer_dependencies(&package_json),
+    }
+  }
+}
+
+/// Reads the `sideEffects` field from the package.json of an npm package,
+/// which is either a boolean or an array of globs of the files that have side
+/// effects. Resolves to null when it's not specified.
+fn resolve_npm_package_side_effects(
+  package_json: &serde_json::Value,
+) -> serde_json::Value {
+  match package_json.get("sideEffects") {
+    Some(
+      value @ (serde_json::Value::Bool(_) | serde_json::Value::Array(_)),
+    ) => value.clone(),
+    _ => serde_json::Value::Null,
+  }
+}
+
+/// Reads the deprecation message of an npm package. The registry information
+/// cached by the npm resolver doesn't keep the `deprecated` field, so this
+/// reads it from the package.json, which is what the registry serves it from.
+fn resolve_npm_package_deprecation(
+  package_json: &serde_json::Value,
+) -> Option<String> {
+  match package_json.get("deprecated")? {
+    serde_json::Value::String(message) if !message.is_empty() => {
+      Some(message.to_string())
+    }
+    _ => None,
+  }
+}
+
+/// The npm lifecycle scripts that run when a package is installed.
+const NPM_INSTALL_SCRIPTS: [&str; 3] = ["preinstall", "install", "postinstall"];
+
+/// Reads the install scripts that an npm package declares in the `scripts`
+/// field of its package.json.
+fn resolve_npm_package_install_scripts(
+  package_json: &serde_json::Value,
+) -> Vec<&'static str> {
+  let Some(scripts) = package_json.get("scripts").and_then(|s| s.as_object())
+  else {
+    return Vec::new();
+  };
+  NPM_INSTALL_SCRIPTS
+    .into_iter()
+    .filter(|name| {
+      matches!(scripts.get(*name), Some(serde_json::Value::String(_)))
+    })
+    .collect()
+}
+
+/// A peer dependency that an npm package declares in its package.json.
+#[derive(Debug, Clone)]
+pub struct NpmPeerDependency {
+  pub name: String,
+  pub version_req: String,
+  /// Whether it's marked as optional in `peerDependenciesMeta`, in which
+  /// case it's fine for it to not be installed.
+  pub optional: bool,
+}
+
+/// Reads the peer dependencies that an npm package declares in the
+/// `peerDependencies` field of its package.json, sorted by name.
+fn resolve_npm_package_peer_dependencies(
+  package_json: &serde_json::Value,
+) -> Vec<NpmPeerDependency> {
+  let Some(peer_dependencies) = package_json
+    .get("peerDependencies")
+    .and_then(|peers| peers.as_object())
+  else {
+    return Vec::new();
+  };
+  let peer_dependencies_meta = package_json.get("peerDependenciesMeta");
+  let mut peer_dependencies = peer_dependencies
+    .iter()
+    .filter_map(|(name, version_req)| {
+      let optional = peer_dependencies_meta
+        .and_then(|meta| meta.get(name))
+        .and_then(|meta| meta.get("optional"))
+        .and_then(|optional| optional.as_bool())
+        .unwrap_or(false);
+      Some(NpmPeerDependency {
+        name: name.to_string(),
+        version_req: version_req.as_str()?.to_string(),
+        optional,
+      })
+    })
+    .collect::<Vec<_>>();
+  peer_dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+  peer_dependencies
+}
+
+/// Whether the entrypoint of an npm package is loaded as an ES module or as a
+/// CommonJS module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NpmModuleFormat {
+  Esm,
+  Cjs,
+}
+
+impl NpmModuleFormat {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      NpmModuleFormat::Esm => "esm",
+      NpmModuleFormat::Cjs => "cjs",
+    }
+  }
+}
+
+/// Determines the module format of an npm package from the `main`, `type`
+/// and `module` fields of its package.json.
+fn resolve_npm_package_module_format(
+  package_json: &serde_json::Value,
+) -> NpmModuleFormat {
+  let maybe_main = package_json.get("main").and_then(|main| main.as_str());
+  // the extension of the entrypoint takes precedence over the `type` field
+  if let Some(main) = maybe_main {
+    if main.ends_with(".mjs") {
+      return NpmModuleFormat::Esm;
+    } else if main.ends_with(".cjs") {
+      return NpmModuleFormat::Cjs;
+    }
+  }
+  let is_esm = package_json.get("type").and_then(|t| t.as_str())
+    == Some("module")
+    // packages with only a `module` entrypoint can only be imported
+    || (maybe_main.is_none() && package_json.get("module").is_some());
+  if is_esm {
+    NpmModuleFormat::Esm
+  } else {
+    NpmModuleFormat::Cjs
+  }
+}
+
+/// Gets the URL of the tarball that the package was downloaded from, which is
+/// provided by the registry so it also points at custom registries.
+pub fn npm_package_tarball(package: &NpmResolutionPackage) -> Option<&str> {
+  let tarball = package.dist.tarball.as_str();
+  (!tarball.is_empty()).then_some(tarball)
+}
+
+/// Relative specifiers of the `import`, `export ... from` and `require()`
+/// statements. This doesn't need to understand the syntax since it's only
+/// used to estimate which files of an npm package get loaded.
+static RELATIVE_IMPORT_RE: Lazy<Regex> = lazy_regex!(
+  r#"(?:\brequire\s*\(\s*|\bimport\s*\(\s*|\bfrom\s*|\bimport\s*)["'](\.\.?(?:/[^"']*)?)["']"#
+);
+
+/// Size of an npm package on disk compared to the size of its files that
+/// can be reached from the imports.
+pub struct NpmPackageUsage {
+  pub id: NpmPackageId,
+  pub size: u64,
+  pub used_size: u64,
+}
+
+impl NpmPackageUsage {
+  pub fn unused_size(&self) -> u64 {
+    self.size.saturating_sub(self.used_size)
+  }
+}
+
+/// Estimates how much of each npm package is used, sorted by the unused size
+/// in descending order. The files are followed from the sub paths imported
+/// by the graph, while the packages only depended on by other packages are
+/// assumed to be loaded through their main entrypoint.
+pub fn find_npm_package_usages(
+  graph: &ModuleGraph,
+  npm_resolver: &CliNpmResolver,
+  npm_info: &NpmInfo,
+) -> Vec<NpmPackageUsage> {
+  let mut sub_paths = HashMap::<&NpmPackageId, Vec<Option<&str>>>::new();
+  for module in graph.modules() {
+    if let Module::Npm(module) = module {
+      if let Some(id) = npm_info.resolved_ids.get(&module.nv_reference.nv) {
+        sub_paths
+          .entry(id)
+          .or_default()
+          .push(module.nv_reference.sub_path.as_deref());
+      }
+    }
+  }
+
+  let mut usages = npm_info
+    .packages
+    .keys()
+    .filter_map(|id| {
+      let size = *npm_info.package_sizes.get(id)?;
+      let folder = npm_resolver.resolve_pkg_folder_from_pkg_id(id).ok()?;
+      let sub_paths = sub_paths.get(id).cloned().unwrap_or_else(|| vec![None]);
+      let used_size = find_npm_package_used_files(&folder, &sub_paths)
+        .values()
+        .sum();
+      Some(NpmPackageUsage {
+        id: id.clone(),
+        size,
+        used_size,
+      })
+    })
+    .collect::<Vec<_>>();
+  usages.sort_by(|a, b| {
+    b.unused_size()
+      .cmp(&a.unused_size())
+      .then_with(|| a.id.as_serialized().cmp(&b.id.as_serialized()))
+  });
+  usages
+}
+
+/// Follows the relative imports from the entrypoints of an npm package to
+/// find the files that get loaded along with their size. The package.json
+/// is always counted since it's read when resolving the package.
+fn find_npm_package_used_files(
+  package_folder: &Path,
+  sub_paths: &[Option<&str>],
+) -> HashMap<PathBuf, u64> {
+  let mut pending = sub_paths
+    .iter()
+    .filter_map(|sub_path| match sub_path {
+      Some(sub_path) => {
+        resolve_npm_package_file(&package_folder.join(sub_path))
+      }
+      None => resolve_npm_package_main(package_folder),
+    })
+    .collect::<Vec<_>>();
+  pending.push(package_folder.join("package.json"));
+
+  let mut files = HashMap::new();
+  while let Some(path) = pending.pop() {
+    let path = deno_core::normalize_path(path);
+    if files.contains_key(&path) || !path.starts_with(package_folder) {
+      continue;
+    }
+    let Ok(source) = std::fs::read(&path) else {
+      continue;
+    };
+    files.insert(path.clone(), source.len() as u64);
+    let is_js = matches!(
+      path.extension().and_then(|ext| ext.to_str()),
+      Some("js" | "cjs" | "mjs")
+    );
+    if !is_js {
+      continue;
+    }
+    let source = String::from_utf8_lossy(&source);
+    let dir = path.parent().unwrap_or(package_folder);
+    for captures in RELATIVE_IMPORT_RE.captures_iter(&source) {
+      if let Some(path) = resolve_npm_package_file(&dir.join(&captures[1])) {
+        pending.push(path);
+      }
+    }
+  }
+  files
+}
+
+/// Gets the file that a package resolves to when imported without a sub
+/// path.
+fn resolve_npm_package_main(package_folder: &Path) -> Option<PathBuf> {
+  let package_json =
+    std::fs::read_to_string(package_folder.join("package.json"))
+      .ok()
+      .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok());
+  let main = package_json
+    .as_ref()
+    .and_then(|package_json| package_json.get("main"))
+    .and_then(|main| main.as_str())
+    .unwrap_or("index.js");
+  resolve_npm_package_file_with_ext(&package_folder.join(main)).or_else(|| {
+    resolve_npm_package_file_with_ext(&package_folder.join("index"))
+  })
+}
+
+/// Resolves a file the way `require()` does, by trying the extensions and
+/// the entrypoint of a directory.
+fn resolve_npm_package_file(path: &Path) -> Option<PathBuf> {
+  if let Some(path) = resolve_npm_package_file_with_ext(path) {
+    return Some(path);
+  }
+  if path.is_dir() {
+    return resolve_npm_package_main(path);
+  }
+  None
+}
+
+fn resolve_npm_package_file_with_ext(path: &Path) -> Option<PathBuf> {
+  if path.is_file() {
+    return Some(path.to_path_buf());
+  }
+  for ext in ["js", "json", "cjs", "mjs", "node"] {
+    let mut path_with_ext = path.as_os_str().to_owned();
+    path_with_ext.push(".");
+    path_with_ext.push(ext);
+    let path_with_ext = PathBuf::from(path_with_ext);
+    if path_with_ext.is_file() {
+      return Some(path_with_ext);
+    }
+  }
+  None
+}
+
+pub fn write_npm_package_usages<TWrite: Write>(
+  usages: &[NpmPackageUsage],
+  writer: &mut TWrite,
+) -> fmt::Result {
+  for usage in usages {
+    writeln!(
+      writer,
+      "npm:{} {} of {} used {}",
+      usage.id.as_serialized(),
+      display::human_size(usage.used_size as f64),
+      display::human_size(usage.size as f64),
+      colors::gray(format!(
+        "({} unused)",
+        display::human_size(usage.unused_size() as f64)
+      )),
+    )?;
+  }
+  let total_size = usages.iter().map(|usage| usage.size).sum::<u64>();
+  let unused_size = usages.iter().map(|usage| usage.unused_size()).sum::<u64>();
+  writeln!(
+    writer,
+    "{} {} of {}",
+    colors::bold("unused size:"),
+    display::human_size(unused_size as f64),
+    display::human_size(total_size as f64),
+  )
+}
+
+/// Reads the license of an npm package from its package.json, supporting
+/// both the `license` field and the deprecated `licenses` array.
+fn resolve_npm_package_license(
+  package_json: &serde_json::Value,
+) -> Option<String> {
+  fn license_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+      serde_json::Value::String(license) => Some(license.to_string()),
+      serde_json::Value::Object(license) => license
+        .get("type")
+        .and_then(|t| t.as_str())
+        .map(|t| t.to_string()),
+      _ => None,
+    }
+  }
+
+  if let Some(license) = package_json.get("license") {
+    return license_text(license);
+  }
+  let licenses = package_json
+    .get("licenses")?
+    .as_array()?
+    .iter()
+    .filter_map(license_text)
+    .collect::<Vec<_>>();
+  if licenses.is_empty() {
+    None
+  } else {
+    Some(licenses.join(" OR "))
+  }
+}
+
+/// Finds the npm packages that were resolved to more than one version,
+/// returning each package name with its versions in ascending order.
+pub fn find_duplicate_npm_packages<'a>(
+  packages: impl Iterator<Item = &'a NpmResolutionPackage>,
+) -> BTreeMap<String, Vec<Version>> {
+  let mut versions_by_name: BTreeMap<String, BTreeSet<Version>> =
+    BTreeMap::new();
+  for package in packages {
+    versions_by_name
+      .entry(package.id.nv.name.clone())
+      .or_default()
+      .insert(package.id.nv.version.clone());
+  }
+  versions_by_name
+    .into_iter()
+    .filter(|(_, versions)| versions.len() > 1)
+    .map(|(name, versions)| (name, versions.into_iter().collect()))
+    .collect()
+}
+
+/// Precached information about npm packages that are used in deno info.
+#[derive(Default)]
+pub struct NpmInfo {
+  pub package_sizes: HashMap<NpmPackageId, u64>,
+  pub package_jsons: HashMap<NpmPackageId, NpmPackageJson>,
+  pub resolved_ids: HashMap<NpmPackageNv, NpmPackageId>,
+  pub packages: HashMap<NpmPackageId, NpmResolutionPackage>,
+  /// Number of unique transitive dependencies of each package.
+  pub transitive_dep_counts: HashMap<NpmPackageId, usize>,
+}
+
+impl NpmInfo {
+  pub fn build<'a>(
+    graph: &'a ModuleGraph,
+    npm_resolver: &'a CliNpmResolver,
+    npm_snapshot: &'a NpmResolutionSnapshot,
+  ) -> Self {
+    let mut info = NpmInfo::default();
+    if graph.npm_packages.is_empty() {
+      return info; // skip going over the modules if there's no npm packages
+    }
+
+    for module in graph.modules() {
+      if let Module::Npm(module) = module {
+        let nv = &module.nv_reference.nv;
+        if let Ok(package) = npm_snapshot.resolve_package_from_deno_module(nv) {
+          info.resolved_ids.insert(nv.clone(), package.id.clone());
+          if !info.packages.contains_key(&package.id) {
+            info.fill_package_info(package, npm_resolver, npm_snapshot);
+          }
+        }
+      }
+    }
+
+    let transitive_dep_counts = info
+      .packages
+      .keys()
+      .map(|id| (id.clone(), info.count_transitive_deps(id)))
+      .collect();
+    info.transitive_dep_counts = transitive_dep_counts;
+    info
+  }
+
+  /// Gets the size of the package along with all its transitive dependencies.
+  pub fn subtree_size(&self, id: &NpmPackageId) -> u64 {
+    self
+      .collect_packages(id)
+      .into_iter()
+      .filter_map(|id| self.package_sizes.get(id))
+      .sum()
+  }
+
+  /// Finds the shortest chain of dependencies from one package to the first
+  /// package that matches the predicate by doing a breadth-first search.
+  pub fn find_package_path<'b>(
+    &'b self,
+    from: &'b NpmPackageId,
+    is_target: impl Fn(&NpmPackageId) -> bool,
+  ) -> Option<Vec<&'b NpmPackageId>> {
+    let mut parents = HashMap::new();
+    let mut visited = HashSet::from([from]);
+    let mut pending = VecDeque::from([from]);
+    while let Some(id) = pending.pop_front() {
+      if is_target(id) {
+        let mut path = vec![id];
+        while let Some(parent) = parents.get(path.last().unwrap()) {
+          path.push(*parent);
+        }
+        path.reverse();
+        return Some(path);
+      }
+      let Some(package) = self.packages.get(id) else {
+        continue;
+      };
+      // sort to pick the same chain when there are several of equal length
+      let mut dep_ids = package.dependencies.values().collect::<Vec<_>>();
+      dep_ids.sort_by_cached_key(|id| id.as_serialized());
+      for dep_id in dep_ids {
+        if visited.insert(dep_id) {
+          parents.insert(dep_id, id);
+          pending.push_back(dep_id);
+        }
+      }
+    }
+    None
+  }
+
+  pub fn count_transitive_deps(&self, id: &NpmPackageId) -> usize {
+    self.collect_packages(id).len() - 1 // don't count the package itself
+  }
+
+  /// Collects the package along with all its transitive dependencies.
+  pub fn collect_packages<'b>(
+    &'b self,
+    id: &'b NpmPackageId,
+  ) -> HashSet<&'b NpmPackageId> {
+    let mut visited = HashSet::from([id]);
+    let mut pending = vec![id];
+    while let Some(id) = pending.pop() {
+      if let Some(package) = self.packages.get(id) {
+        for dep_id in package.dependencies.values() {
+          if visited.insert(dep_id) {
+            pending.push(dep_id);
+          }
+        }
+      }
+    }
+    visited
+  }
+
+  pub fn fill_package_info<'a>(
+    &mut self,
+    package: &NpmResolutionPackage,
+    npm_resolver: &'a CliNpmResolver,
+    npm_snapshot: &'a NpmResolutionSnapshot,
+  ) {
+    self.packages.insert(package.id.clone(), package.clone());
+    if let Ok(size) = npm_resolver.package_size(&package.id) {
+      self.package_sizes.insert(package.id.clone(), size);
+    }
+    self.package_jsons.insert(
+      package.id.clone(),
+      NpmPackageJson::read(npm_resolver, &package.id),
+    );
+    for id in package.dependencies.values() {
+      if !self.packages.contains_key(id) {
+        if let Some(package) = npm_snapshot.package_from_id(id) {
+          self.fill_package_info(package, npm_resolver, npm_snapshot);
+        }
+      }
+    }
+  }
+
+  pub fn resolve_package(
+    &self,
+    nv: &NpmPackageNv,
+  ) -> Option<&NpmResolutionPackage> {
+    let id = self.resolved_ids.get(nv)?;
+    self.packages.get(id)
+  }
+}
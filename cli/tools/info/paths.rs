@@ -0,0 +1,217 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+use deno_core::serde_json::json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_npm::NpmPackageId;
+
+use crate::args::InfoFlags;
+use crate::npm::CliNpmResolver;
+
+use super::esm_dependency_specifiers;
+use super::print_tree_node;
+use super::resolve_tree_width;
+use super::write_json;
+use super::write_output;
+use super::NpmInfo;
+use super::TreeConnectors;
+use super::TreeNode;
+
+/// Writes the modules that import `target` for `--importers`.
+pub fn write_importers(
+  graph: &ModuleGraph,
+  target: &ModuleSpecifier,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  let target = graph.resolve(target);
+  if let Ok(None) = graph.try_get(&target) {
+    bail!("{} was not found in graph.", target);
+  }
+  let importers = find_importers(graph, &target);
+  if info_flags.json {
+    write_json(&json!({ "importers": importers }), info_flags)?;
+  } else {
+    let mut output = String::new();
+    for importer in importers {
+      writeln!(output, "{importer}")?;
+    }
+    write_output(output.as_bytes(), info_flags)?;
+  }
+  Ok(())
+}
+
+/// Writes the shortest chain of imports from the root to `target` for
+/// `--path-to`.
+pub fn write_import_path(
+  graph: &ModuleGraph,
+  target: &ModuleSpecifier,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  let target = graph.resolve(target);
+  if let Ok(None) = graph.try_get(&target) {
+    bail!("{} was not found in graph.", target);
+  }
+  let root = &graph.roots[0];
+  let Some(path) = find_import_path(graph, root, &target) else {
+    bail!("{} is not imported by {}", target, root);
+  };
+  let path = path.iter().map(ToString::to_string).collect();
+  let mut output = String::new();
+  print_tree_node(
+    &import_path_to_tree_node(path),
+    TreeConnectors::from_flags(info_flags),
+    resolve_tree_width(info_flags),
+    &mut output,
+  )?;
+  write_output(output.as_bytes(), info_flags)
+}
+
+/// Writes the import chains that cause an npm package to be included for
+/// `--why`.
+pub fn write_npm_package_chains(
+  graph: &ModuleGraph,
+  npm_resolver: &CliNpmResolver,
+  package: &str,
+  info_flags: &InfoFlags,
+) -> Result<(), AnyError> {
+  let npm_snapshot = npm_resolver.snapshot();
+  let npm_info = NpmInfo::build(graph, npm_resolver, &npm_snapshot);
+  let chains = find_npm_package_chains(graph, &npm_info, package);
+  if chains.is_empty() {
+    bail!("{} is not a dependency of {}", package, graph.roots[0]);
+  }
+  let mut output = String::new();
+  for chain in chains {
+    print_tree_node(
+      &import_path_to_tree_node(chain),
+      TreeConnectors::from_flags(info_flags),
+      resolve_tree_width(info_flags),
+      &mut output,
+    )?;
+  }
+  write_output(output.as_bytes(), info_flags)
+}
+
+/// Finds the modules that import the provided specifier as either a code or
+/// a type dependency.
+fn find_importers<'a>(
+  graph: &'a ModuleGraph,
+  target: &ModuleSpecifier,
+) -> Vec<&'a ModuleSpecifier> {
+  graph
+    .modules()
+    .filter_map(|module| module.esm())
+    .filter(|module| {
+      esm_dependency_specifiers(module)
+        .any(|specifier| graph.resolve(specifier) == *target)
+    })
+    .map(|module| &module.specifier)
+    .collect()
+}
+
+/// Finds the shortest chain of imports from one module to another by doing
+/// a breadth-first search over the code and type dependencies in the graph.
+fn find_import_path(
+  graph: &ModuleGraph,
+  from: &ModuleSpecifier,
+  to: &ModuleSpecifier,
+) -> Option<Vec<ModuleSpecifier>> {
+  let from = graph.resolve(from);
+  let mut parents = HashMap::new();
+  let mut visited = HashSet::from([from.clone()]);
+  let mut pending = VecDeque::from([from]);
+  while let Some(specifier) = pending.pop_front() {
+    if specifier == *to {
+      let mut path = vec![specifier];
+      while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+      }
+      path.reverse();
+      return Some(path);
+    }
+    let Some(module) = graph.get(&specifier).and_then(|m| m.esm()) else {
+      continue;
+    };
+    for dep_specifier in esm_dependency_specifiers(module) {
+      let dep_specifier = graph.resolve(dep_specifier);
+      if visited.insert(dep_specifier.clone()) {
+        parents.insert(dep_specifier.clone(), specifier.clone());
+        pending.push_back(dep_specifier);
+      }
+    }
+  }
+  None
+}
+
+/// Finds the import chains that cause the npm packages matching the provided
+/// `name` or `name@version` to be included. Each chain goes from the root to
+/// an npm specifier in the graph and then through the dependencies between
+/// the npm packages.
+fn find_npm_package_chains(
+  graph: &ModuleGraph,
+  npm_info: &NpmInfo,
+  package: &str,
+) -> Vec<Vec<String>> {
+  let package = package.strip_prefix("npm:").unwrap_or(package);
+  // skip the first character so the @ of a scoped package isn't matched
+  let (name, version) = match package.get(1..).and_then(|p| p.find('@')) {
+    Some(index) => (&package[..index + 1], Some(&package[index + 2..])),
+    None => (package, None),
+  };
+  let is_target = |id: &NpmPackageId| {
+    id.nv.name == name
+      && version.map_or(true, |version| id.nv.version.to_string() == version)
+  };
+
+  let mut chains = Vec::new();
+  for module in graph.modules() {
+    let Module::Npm(module) = module else {
+      continue;
+    };
+    let Some(id) = npm_info.resolved_ids.get(&module.nv_reference.nv) else {
+      continue;
+    };
+    let Some(package_path) = npm_info.find_package_path(id, is_target) else {
+      continue;
+    };
+    let Some(mut import_path) =
+      find_import_path(graph, &graph.roots[0], &module.specifier)
+    else {
+      continue;
+    };
+    // the npm specifier is shown as the package it resolved to
+    import_path.pop();
+    chains.push(
+      import_path
+        .iter()
+        .map(ToString::to_string)
+        .chain(
+          package_path
+            .into_iter()
+            .map(|id| format!("npm:{}", id.as_serialized())),
+        )
+        .collect(),
+    );
+  }
+  chains
+}
+
+/// Builds a tree where each module in the import path is the only child of
+/// the module that imports it.
+fn import_path_to_tree_node(path: Vec<String>) -> TreeNode {
+  let mut nodes = path.into_iter().rev().map(TreeNode::from_text);
+  let mut tree_node = nodes.next().unwrap();
+  for mut parent in nodes {
+    parent.children.push(tree_node);
+    tree_node = parent;
+  }
+  tree_node
+}
@@ -0,0 +1,511 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write;
+
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_core::serde_json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_npm::NpmResolutionPackage;
+use deno_runtime::colors;
+
+use crate::display;
+
+use super::esm_dependency_specifiers;
+use super::find_runtime_modules;
+use super::maybe_module_size;
+use super::maybe_size_to_text;
+use super::npm_package_tarball;
+use super::NpmInfo;
+
+/// Finds the largest modules and npm packages of the graph, sorted by size
+/// and then by specifier, keeping the first `count` of them.
+pub fn find_largest_entries(
+  graph: &ModuleGraph,
+  npm_info: &NpmInfo,
+  count: usize,
+) -> Vec<(String, u64)> {
+  let mut entries = graph
+    .modules()
+    .filter_map(|module| {
+      Some((module.specifier().to_string(), maybe_module_size(module)?))
+    })
+    .chain(
+      npm_info
+        .package_sizes
+        .iter()
+        .map(|(id, size)| (format!("npm:{}", id.as_serialized()), *size)),
+    )
+    .collect::<Vec<_>>();
+  entries.sort_by(|(a_specifier, a_size), (b_specifier, b_size)| {
+    b_size
+      .cmp(a_size)
+      .then_with(|| a_specifier.cmp(b_specifier))
+  });
+  entries.truncate(count);
+  entries
+}
+
+pub fn write_largest_entries<TWrite: Write>(
+  entries: &[(String, u64)],
+  writer: &mut TWrite,
+) -> fmt::Result {
+  let rank_width = entries.len().to_string().len();
+  for (index, (specifier, size)) in entries.iter().enumerate() {
+    writeln!(
+      writer,
+      "{:>width$}. {} {}",
+      index + 1,
+      specifier,
+      maybe_size_to_text(Some(*size)),
+      width = rank_width
+    )?;
+  }
+  Ok(())
+}
+
+#[derive(Default)]
+pub struct ModuleSummary {
+  pub module_count: usize,
+  pub size: u64,
+}
+
+/// Groups the modules of the graph by the host they were loaded from. Local
+/// modules are grouped under `local` and specifiers without a host, like
+/// `npm:` or `data:` ones, are grouped by their scheme.
+pub fn summarize_modules_by_host(
+  graph: &ModuleGraph,
+) -> BTreeMap<String, ModuleSummary> {
+  let mut summaries = BTreeMap::<String, ModuleSummary>::new();
+  for module in graph.modules() {
+    let specifier = module.specifier();
+    let host = match specifier.scheme() {
+      "file" => "local".to_string(),
+      scheme => specifier.host_str().unwrap_or(scheme).to_string(),
+    };
+    let summary = summaries.entry(host).or_default();
+    summary.module_count += 1;
+    summary.size += match module {
+      Module::Esm(module) => module.size() as u64,
+      Module::Json(module) => module.size() as u64,
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => 0,
+    };
+  }
+  summaries
+}
+
+/// Groups the modules of the graph by their media type. Modules without a
+/// media type, like npm packages, are grouped by their kind.
+pub fn summarize_modules_by_media_type(
+  graph: &ModuleGraph,
+) -> BTreeMap<String, ModuleSummary> {
+  let mut summaries = BTreeMap::<String, ModuleSummary>::new();
+  for module in graph.modules() {
+    let (media_type, size) = match module {
+      Module::Esm(module) => (module.media_type.to_string(), module.size()),
+      Module::Json(module) => (module.media_type.to_string(), module.size()),
+      Module::Npm(_) => ("npm".to_string(), 0),
+      Module::Node(_) => ("node".to_string(), 0),
+      Module::External(_) => ("external".to_string(), 0),
+    };
+    let summary = summaries.entry(media_type).or_default();
+    summary.module_count += 1;
+    summary.size += size as u64;
+  }
+  summaries
+}
+
+pub fn write_summary_table<TWrite: Write>(
+  key_header: &str,
+  summaries: &BTreeMap<String, ModuleSummary>,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  const MODULES_HEADER: &str = "modules";
+  let key_width = summaries
+    .keys()
+    .map(|key| key.len())
+    .chain([key_header.len()])
+    .max()
+    .unwrap();
+  writeln!(
+    writer,
+    "{}",
+    colors::bold(format!(
+      "{:<key_width$}  {:>width$}  size",
+      key_header,
+      MODULES_HEADER,
+      width = MODULES_HEADER.len()
+    ))
+  )?;
+  for (key, summary) in summaries {
+    writeln!(
+      writer,
+      "{:<key_width$}  {:>width$}  {}",
+      key,
+      summary.module_count,
+      display::human_size(summary.size as f64),
+      width = MODULES_HEADER.len()
+    )?;
+  }
+  Ok(())
+}
+
+/// Counts the lines of source code in the ESM and JSON modules of the graph.
+/// npm packages are excluded because their sources aren't loaded.
+pub fn count_source_lines(graph: &ModuleGraph) -> usize {
+  graph
+    .modules()
+    .map(|module| match module {
+      Module::Esm(module) => module.source.lines().count(),
+      Module::Json(module) => module.source.lines().count(),
+      Module::Node(_) | Module::Npm(_) | Module::External(_) => 0,
+    })
+    .sum()
+}
+
+/// The total size split into the roots, the modules they depend on and the
+/// npm packages, shown with `--split-sizes`.
+pub struct SplitSizes {
+  pub root: u64,
+  pub deps: u64,
+  pub npm: u64,
+}
+
+impl SplitSizes {
+  pub fn compute(graph: &ModuleGraph, npm_info: &NpmInfo) -> Self {
+    let root_specifiers = graph
+      .roots
+      .iter()
+      .map(|root| graph.resolve(root))
+      .collect::<HashSet<_>>();
+    let mut root = 0;
+    let mut deps = 0;
+    for module in graph.modules() {
+      let size = maybe_module_size(module).unwrap_or(0);
+      if root_specifiers.contains(module.specifier()) {
+        root += size;
+      } else {
+        deps += size;
+      }
+    }
+    Self {
+      root,
+      deps,
+      npm: npm_info.package_sizes.values().sum(),
+    }
+  }
+}
+
+/// Counts the dependencies in the graph that are loaded from the file system
+/// and from remote servers. The roots, the modules that resolve to npm
+/// packages, and the node builtins aren't counted.
+pub fn count_local_and_remote_modules(graph: &ModuleGraph) -> (usize, usize) {
+  let root_specifiers = graph
+    .roots
+    .iter()
+    .map(|root| graph.resolve(root))
+    .collect::<HashSet<_>>();
+  let mut local_count = 0;
+  let mut remote_count = 0;
+  for module in graph.modules() {
+    let specifier = module.specifier();
+    if root_specifiers.contains(specifier)
+      || matches!(module, Module::Npm(_) | Module::Node(_))
+    {
+      continue;
+    }
+    if matches!(specifier.scheme(), "http" | "https") {
+      remote_count += 1;
+    } else {
+      local_count += 1;
+    }
+  }
+  (local_count, remote_count)
+}
+
+/// Whether a module is transpiled before it's run, which is the case for
+/// TypeScript and JSX.
+pub fn module_needs_emit(module: &Module) -> bool {
+  module.esm().map_or(false, |module| {
+    matches!(
+      module.media_type,
+      MediaType::TypeScript
+        | MediaType::Mts
+        | MediaType::Cts
+        | MediaType::Jsx
+        | MediaType::Tsx
+    )
+  })
+}
+
+/// Counts the dependencies in the graph that are loaded at runtime and the
+/// ones that are only reachable through type-only dependencies, which only
+/// exist for type checking. The roots aren't counted.
+pub fn count_runtime_and_type_only_deps(graph: &ModuleGraph) -> (usize, usize) {
+  let root_specifiers = graph
+    .roots
+    .iter()
+    .map(|root| graph.resolve(root))
+    .collect::<HashSet<_>>();
+  let runtime_modules = find_runtime_modules(graph);
+  let mut runtime_count = 0;
+  let mut type_only_count = 0;
+  for module in graph.modules() {
+    let specifier = module.specifier();
+    if root_specifiers.contains(specifier) {
+      continue;
+    }
+    if runtime_modules.contains(specifier) {
+      runtime_count += 1;
+    } else {
+      type_only_count += 1;
+    }
+  }
+  (runtime_count, type_only_count)
+}
+
+/// Counts the dynamic imports of every module in the graph. An import site
+/// is counted once per module even when it's imported dynamically more than
+/// once, the same way the dependencies of a module are keyed.
+pub fn count_dynamic_imports(graph: &ModuleGraph) -> usize {
+  graph
+    .modules()
+    .filter_map(|module| module.esm())
+    .map(|module| {
+      module
+        .dependencies
+        .values()
+        .filter(|dep| dep.is_dynamic)
+        .count()
+    })
+    .sum()
+}
+
+/// Gets the distinct hosts of the registries that the npm packages were
+/// downloaded from.
+pub fn find_npm_registry_hosts<'a>(
+  packages: impl Iterator<Item = &'a NpmResolutionPackage>,
+) -> BTreeSet<String> {
+  packages
+    .filter_map(|package| {
+      let url =
+        deno_core::url::Url::parse(npm_package_tarball(package)?).ok()?;
+      let host = url.host_str()?;
+      Some(match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+      })
+    })
+    .collect()
+}
+
+/// Gets the length of the longest chain of imports starting at any of the
+/// roots of the graph.
+pub fn find_graph_max_depth(graph: &ModuleGraph) -> usize {
+  let mut depths = HashMap::new();
+  graph
+    .roots
+    .iter()
+    .map(|root| {
+      find_max_depth(
+        graph,
+        &graph.resolve(root),
+        &mut HashSet::new(),
+        &mut depths,
+      )
+    })
+    .max()
+    .unwrap_or(0)
+}
+
+/// Gets the length of the longest acyclic chain of imports starting at the
+/// module. The modules on the current chain are tracked in `visiting` so
+/// that imports forming a cycle are not followed, and the depth of each
+/// module is memoized in `depths` once it has been computed.
+fn find_max_depth(
+  graph: &ModuleGraph,
+  specifier: &ModuleSpecifier,
+  visiting: &mut HashSet<ModuleSpecifier>,
+  depths: &mut HashMap<ModuleSpecifier, usize>,
+) -> usize {
+  if let Some(depth) = depths.get(specifier) {
+    return *depth;
+  }
+  let Some(module) = graph.get(specifier).and_then(|m| m.esm()) else {
+    return 0;
+  };
+  visiting.insert(specifier.clone());
+  let mut max_depth = 0;
+  for dep_specifier in esm_dependency_specifiers(module) {
+    let dep_specifier = graph.resolve(dep_specifier);
+    if visiting.contains(&dep_specifier) || graph.get(&dep_specifier).is_none()
+    {
+      continue;
+    }
+    let depth = find_max_depth(graph, &dep_specifier, visiting, depths) + 1;
+    max_depth = max_depth.max(depth);
+  }
+  visiting.remove(specifier);
+  depths.insert(specifier.clone(), max_depth);
+  max_depth
+}
+
+/// Gets the size of each module along with all the unique modules it
+/// transitively imports. The contents of npm packages aren't included.
+///
+/// The modules of an import cycle share the same subtree, so the subtrees
+/// are computed per strongly connected component. The components are found
+/// in post-order, which lets each one reuse the already computed subtrees of
+/// the components it imports, so every module is only visited once.
+pub fn find_subtree_sizes(
+  graph: &ModuleGraph,
+) -> HashMap<ModuleSpecifier, u64> {
+  let modules = graph.modules().collect::<Vec<_>>();
+  let module_indexes = modules
+    .iter()
+    .enumerate()
+    .map(|(index, module)| (module.specifier(), index))
+    .collect::<HashMap<_, _>>();
+  let deps = modules
+    .iter()
+    .map(|module| {
+      let Some(module) = module.esm() else {
+        return Vec::new();
+      };
+      esm_dependency_specifiers(module)
+        .filter_map(|specifier| {
+          module_indexes.get(&graph.resolve(specifier)).copied()
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  let components = find_strongly_connected_components(&deps);
+  let mut module_components = vec![0; modules.len()];
+  for (component_index, members) in components.iter().enumerate() {
+    for &member in members {
+      module_components[member] = component_index;
+    }
+  }
+  // the components each component reaches, including itself
+  let mut reachable_components: Vec<HashSet<usize>> =
+    Vec::with_capacity(components.len());
+  let mut component_sizes = Vec::with_capacity(components.len());
+  let mut subtree_sizes = HashMap::with_capacity(modules.len());
+  for (component_index, members) in components.iter().enumerate() {
+    let mut reachable = HashSet::from([component_index]);
+    for &member in members {
+      for &dep in &deps[member] {
+        let dep_component = module_components[dep];
+        // a reached component already brought along everything it reaches
+        if !reachable.contains(&dep_component) {
+          reachable.extend(reachable_components[dep_component].iter().copied());
+        }
+      }
+    }
+    component_sizes.push(
+      members
+        .iter()
+        .map(|&member| maybe_module_size(modules[member]).unwrap_or(0))
+        .sum::<u64>(),
+    );
+    let subtree_size = reachable
+      .iter()
+      .map(|&component| component_sizes[component])
+      .sum::<u64>();
+    for &member in members {
+      subtree_sizes.insert(modules[member].specifier().clone(), subtree_size);
+    }
+    reachable_components.push(reachable);
+  }
+  subtree_sizes
+}
+
+/// Splits a graph, given as the dependencies of each node, into its strongly
+/// connected components with Tarjan's algorithm. The components are returned
+/// in post-order, so a component always comes after the components that it
+/// depends on.
+fn find_strongly_connected_components(deps: &[Vec<usize>]) -> Vec<Vec<usize>> {
+  const UNVISITED: usize = usize::MAX;
+  let mut indexes = vec![UNVISITED; deps.len()];
+  let mut low_links = vec![0; deps.len()];
+  let mut on_stack = vec![false; deps.len()];
+  let mut stack = Vec::new();
+  let mut components = Vec::new();
+  let mut next_index = 0;
+  for start in 0..deps.len() {
+    if indexes[start] != UNVISITED {
+      continue;
+    }
+    // the nodes being visited along with the position of the next dependency
+    // to check, which replaces the recursion so deep graphs can't overflow
+    let mut visiting = vec![(start, 0)];
+    indexes[start] = next_index;
+    low_links[start] = next_index;
+    next_index += 1;
+    stack.push(start);
+    on_stack[start] = true;
+    while let Some((node, dep_position)) = visiting.last_mut() {
+      let node = *node;
+      if let Some(&dep) = deps[node].get(*dep_position) {
+        *dep_position += 1;
+        if indexes[dep] == UNVISITED {
+          indexes[dep] = next_index;
+          low_links[dep] = next_index;
+          next_index += 1;
+          stack.push(dep);
+          on_stack[dep] = true;
+          visiting.push((dep, 0));
+        } else if on_stack[dep] {
+          low_links[node] = low_links[node].min(indexes[dep]);
+        }
+        continue;
+      }
+      visiting.pop();
+      if let Some(&(parent, _)) = visiting.last() {
+        low_links[parent] = low_links[parent].min(low_links[node]);
+      }
+      if low_links[node] == indexes[node] {
+        let mut component = Vec::new();
+        while let Some(member) = stack.pop() {
+          on_stack[member] = false;
+          component.push(member);
+          if member == node {
+            break;
+          }
+        }
+        components.push(component);
+      }
+    }
+  }
+  components
+}
+
+pub fn add_subtree_sizes_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+) {
+  let subtree_sizes = find_subtree_sizes(graph);
+  let Some(modules) = json.get_mut("modules").and_then(|m| m.as_array_mut())
+  else {
+    return;
+  };
+  for module in modules {
+    let Some(specifier) = module
+      .get("specifier")
+      .and_then(|s| s.as_str())
+      .and_then(|s| ModuleSpecifier::parse(s).ok())
+    else {
+      continue;
+    };
+    if let Some(subtree_size) = subtree_sizes.get(&specifier) {
+      module["subtreeSize"] = (*subtree_size).into();
+    }
+  }
+}
@@ -0,0 +1,373 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write;
+
+use deno_ast::ModuleSpecifier;
+use deno_graph::ModuleGraph;
+use deno_runtime::colors;
+
+use crate::args::InfoFlags;
+use crate::util::console::console_size;
+
+pub struct TreeNode {
+  pub text: String,
+  pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+  pub fn from_text(text: String) -> Self {
+    Self {
+      text,
+      children: Default::default(),
+    }
+  }
+}
+
+/// The characters used to draw the branches of the tree.
+#[derive(Clone, Copy)]
+pub struct TreeConnectors {
+  pub sibling: char,
+  pub last_sibling: char,
+  pub child_deps: char,
+  pub child_no_deps: char,
+  pub horizontal: char,
+  pub vertical: char,
+  pub empty: char,
+}
+
+impl TreeConnectors {
+  const UNICODE: TreeConnectors = TreeConnectors {
+    sibling: '├',
+    last_sibling: '└',
+    child_deps: '┬',
+    child_no_deps: '─',
+    horizontal: '─',
+    vertical: '│',
+    empty: ' ',
+  };
+
+  /// For terminals and log viewers that can't display box-drawing characters.
+  const ASCII: TreeConnectors = TreeConnectors {
+    sibling: '|',
+    last_sibling: '`',
+    child_deps: '+',
+    child_no_deps: '-',
+    horizontal: '-',
+    vertical: '|',
+    empty: ' ',
+  };
+
+  pub fn from_flags(info_flags: &InfoFlags) -> Self {
+    if info_flags.ascii {
+      Self::ASCII
+    } else {
+      Self::UNICODE
+    }
+  }
+}
+
+/// Gets the number of columns the lines of the tree are shortened to, which is
+/// the `--width` or the width of the terminal. Output that isn't written to a
+/// terminal is left as is so it can be processed by other tools.
+pub fn resolve_tree_width(info_flags: &InfoFlags) -> Option<usize> {
+  if let Some(width) = info_flags.width {
+    return Some(width as usize);
+  }
+  if info_flags.out.is_some() || !atty::is(atty::Stream::Stdout) {
+    return None;
+  }
+  console_size().map(|size| size.cols as usize)
+}
+
+/// Shortens text to the given number of columns, ending it with `…` when it
+/// doesn't fit. The ANSI escape codes don't take up any columns, so they're
+/// kept and the colors are reset after the cut.
+fn truncate_to_width(text: &str, width: usize) -> Cow<str> {
+  let mut column = 0;
+  let mut in_escape_code = false;
+  let mut maybe_cut_index = None;
+  for (index, c) in text.char_indices() {
+    if in_escape_code {
+      in_escape_code = !c.is_ascii_alphabetic();
+      continue;
+    }
+    if c == '\x1b' {
+      in_escape_code = true;
+      continue;
+    }
+    if column + 1 == width {
+      // leave room for the ellipsis
+      maybe_cut_index = Some(index);
+    }
+    column += 1;
+    if column > width {
+      let cut_index = maybe_cut_index.unwrap_or(0);
+      let reset = if text.contains('\x1b') { "\x1b[0m" } else { "" };
+      return Cow::Owned(format!("{}…{}", &text[..cut_index], reset));
+    }
+  }
+  Cow::Borrowed(text)
+}
+
+pub fn print_tree_node<TWrite: Write>(
+  tree_node: &TreeNode,
+  connectors: TreeConnectors,
+  max_width: Option<usize>,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  fn print_children<TWrite: Write>(
+    writer: &mut TWrite,
+    connectors: TreeConnectors,
+    max_width: Option<usize>,
+    prefix: &str,
+    children: &Vec<TreeNode>,
+  ) -> fmt::Result {
+    let child_len = children.len();
+    for (index, child) in children.iter().enumerate() {
+      let is_last = index + 1 == child_len;
+      write_tree_child(
+        &child.text,
+        connectors,
+        max_width,
+        prefix,
+        is_last,
+        !child.children.is_empty(),
+        writer,
+      )?;
+      print_children(
+        writer,
+        connectors,
+        max_width,
+        &tree_child_prefix(connectors, prefix, is_last),
+        &child.children,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  write_tree_root(&tree_node.text, max_width, writer)?;
+  print_children(writer, connectors, max_width, "", &tree_node.children)?;
+  Ok(())
+}
+
+pub fn write_tree_root<TWrite: Write>(
+  text: &str,
+  max_width: Option<usize>,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  match max_width {
+    Some(max_width) => {
+      writeln!(writer, "{}", truncate_to_width(text, max_width))
+    }
+    None => writeln!(writer, "{}", text),
+  }
+}
+
+/// Writes the line of a node below the root, with the connectors to its
+/// siblings and children after the prefix drawn by its ancestors.
+pub fn write_tree_child<TWrite: Write>(
+  text: &str,
+  connectors: TreeConnectors,
+  max_width: Option<usize>,
+  prefix: &str,
+  is_last: bool,
+  has_children: bool,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  let sibling_connector = if is_last {
+    connectors.last_sibling
+  } else {
+    connectors.sibling
+  };
+  let horizontal_connector = connectors.horizontal;
+  let child_connector = if has_children {
+    connectors.child_deps
+  } else {
+    connectors.child_no_deps
+  };
+  let text = match max_width {
+    // the connectors take up three columns followed by a space
+    Some(max_width) => truncate_to_width(
+      text,
+      max_width.saturating_sub(prefix.chars().count() + 4),
+    ),
+    None => Cow::Borrowed(text),
+  };
+  writeln!(
+    writer,
+    "{} {}",
+    colors::gray(format!(
+      "{prefix}{sibling_connector}{horizontal_connector}{child_connector}"
+    )),
+    text
+  )
+}
+
+/// Gets the prefix of the lines of the children of a node.
+pub fn tree_child_prefix(
+  connectors: TreeConnectors,
+  prefix: &str,
+  is_last: bool,
+) -> String {
+  format!(
+    "{}{}{}",
+    prefix,
+    if is_last {
+      connectors.empty
+    } else {
+      connectors.vertical
+    },
+    connectors.empty
+  )
+}
+
+/// Writes the key that explains the connectors, markers and colors of the
+/// tree for `--legend`.
+pub fn write_tree_legend<TWrite: Write>(
+  connectors: TreeConnectors,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  let pad = |key: &str| format!("{key:<12}");
+  let TreeConnectors {
+    sibling,
+    last_sibling,
+    child_deps,
+    child_no_deps,
+    horizontal,
+    ..
+  } = connectors;
+  writeln!(writer, "{}", colors::bold("legend:"))?;
+  writeln!(
+    writer,
+    "  {} a dependency",
+    colors::gray(pad(&format!(
+      "{sibling}{horizontal}{child_no_deps} {last_sibling}{horizontal}{child_no_deps}"
+    )))
+  )?;
+  writeln!(
+    writer,
+    "  {} a dependency with dependencies of its own",
+    colors::gray(pad(&format!(
+      "{sibling}{horizontal}{child_deps} {last_sibling}{horizontal}{child_deps}"
+    )))
+  )?;
+  writeln!(
+    writer,
+    "  {} already shown above, so its dependencies aren't repeated",
+    colors::gray(pad("*"))
+  )?;
+  writeln!(
+    writer,
+    "  {} imports a module that imports it",
+    colors::yellow(pad("(circular)"))
+  )?;
+  writeln!(
+    writer,
+    "  {} loaded with a dynamic import",
+    colors::cyan(pad("(dynamic)"))
+  )?;
+  writeln!(
+    writer,
+    "  {} resolved with the import map",
+    colors::gray(pad("(mapped)"))
+  )?;
+  writeln!(
+    writer,
+    "  {} could not be found",
+    colors::red_bold(pad("(missing)"))
+  )?;
+  writeln!(
+    writer,
+    "  {} could not be loaded, with the reason after it",
+    colors::red(pad("red"))
+  )?;
+  writeln!(
+    writer,
+    "  {} only imported for its types",
+    colors::italic(pad("italic"))
+  )?;
+  Ok(())
+}
+
+/// Short names for the prefixes shared by the remote specifiers in the graph,
+/// like `std/` for `https://deno.land/std@0.200.0/`.
+pub struct SpecifierAbbreviations {
+  /// The abbreviation along with the prefix it stands for, sorted by prefix.
+  pub prefixes: Vec<(String, String)>,
+}
+
+impl SpecifierAbbreviations {
+  pub fn build(graph: &ModuleGraph) -> Self {
+    let mut candidates = BTreeMap::<String, (String, usize)>::new();
+    for module in graph.modules() {
+      if let Some((prefix, name)) = abbreviation_prefix(module.specifier()) {
+        candidates.entry(prefix).or_insert((name, 0)).1 += 1;
+      }
+    }
+    let mut name_counts = HashMap::<String, usize>::new();
+    let prefixes = candidates
+      .into_iter()
+      // abbreviating a prefix used by a single module doesn't help
+      .filter(|(_, (_, module_count))| *module_count > 1)
+      .map(|(prefix, (name, _))| {
+        let name_count = name_counts.entry(name.clone()).or_default();
+        *name_count += 1;
+        let abbreviation = if *name_count == 1 {
+          format!("{name}/")
+        } else {
+          format!("{name}~{name_count}/")
+        };
+        (abbreviation, prefix)
+      })
+      .collect();
+    Self { prefixes }
+  }
+
+  pub fn abbreviate(&self, specifier: &str) -> String {
+    // the origin of a host is also a prefix of its versioned paths
+    let longest_match = self
+      .prefixes
+      .iter()
+      .filter(|(_, prefix)| specifier.starts_with(prefix.as_str()))
+      .max_by_key(|(_, prefix)| prefix.len());
+    match longest_match {
+      Some((abbreviation, prefix)) => {
+        format!("{}{}", abbreviation, &specifier[prefix.len()..])
+      }
+      None => specifier.to_string(),
+    }
+  }
+}
+
+/// Gets the prefix of a remote specifier up to its first versioned path
+/// segment (ex. `https://deno.land/std@0.200.0/`) or its origin when there
+/// is none, along with a name to abbreviate it with.
+fn abbreviation_prefix(
+  specifier: &ModuleSpecifier,
+) -> Option<(String, String)> {
+  if !matches!(specifier.scheme(), "http" | "https") {
+    return None;
+  }
+  let origin = specifier.join("/").ok()?.to_string();
+  let segments = specifier.path_segments()?.collect::<Vec<_>>();
+  // the last segment is the file name, so it's never part of the prefix
+  let directories = &segments[..segments.len().saturating_sub(1)];
+  for (i, segment) in directories.iter().enumerate() {
+    // the @ of a scope isn't a version separator
+    let Some(version_index) = segment.get(1..).and_then(|s| s.find('@')) else {
+      continue;
+    };
+    let mut name = segment[..version_index + 1].to_string();
+    if i > 0 && directories[i - 1].starts_with('@') {
+      name = format!("{}/{}", directories[i - 1], name);
+    }
+    let prefix = format!("{}{}/", origin, directories[..=i].join("/"));
+    return Some((prefix, name));
+  }
+  Some((origin, specifier.host_str()?.to_string()))
+}
@@ -0,0 +1,98 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+use deno_npm::NpmPackageId;
+
+use crate::args::InfoFlags;
+use crate::npm::CliNpmResolver;
+
+use super::maybe_module_size;
+use super::GraphDisplayContext;
+
+impl<'a> GraphDisplayContext<'a> {
+  /// Builds the dependency tree as nested `{ name, value, children }` nodes,
+  /// which is the shape that treemap libraries like D3 expect.
+  pub fn build_treemap_json(
+    graph: &'a ModuleGraph,
+    npm_resolver: &'a CliNpmResolver,
+    info_flags: &InfoFlags,
+  ) -> serde_json::Value {
+    let mut context = Self::new(graph, npm_resolver, info_flags);
+    let root = graph.resolve(&graph.roots[0]);
+    match graph.get(&root) {
+      Some(module) => context.build_treemap_module(module),
+      None => json!({ "name": root, "value": 0 }),
+    }
+  }
+
+  /// The value is the size of the module itself. Modules that were already
+  /// added have a value of 0 and no children so they're only counted once.
+  fn build_treemap_module(&mut self, module: &Module) -> serde_json::Value {
+    if let Some(package) = module
+      .npm()
+      .and_then(|npm| self.npm_info.resolve_package(&npm.nv_reference.nv))
+    {
+      let package_id = package.id.clone();
+      return self.build_treemap_package(&package_id);
+    }
+    let specifier = module.specifier();
+    if self.seen.insert(specifier.to_string(), None).is_some() {
+      return json!({ "name": specifier, "value": 0 });
+    }
+    let mut children = Vec::new();
+    if let Some(module) = module.esm() {
+      let resolutions = module
+        .maybe_types_dependency
+        .iter()
+        .map(|types_dep| &types_dep.dependency)
+        .chain(
+          module
+            .dependencies
+            .values()
+            .flat_map(|dep| [&dep.maybe_code, &dep.maybe_type]),
+        );
+      for resolution in resolutions {
+        let Some(resolved) = resolution.maybe_specifier() else {
+          continue;
+        };
+        if let Some(child) = self.graph.get(&self.graph.resolve(resolved)) {
+          children.push(self.build_treemap_module(child));
+        }
+      }
+    }
+    json!({
+      "name": specifier,
+      "value": maybe_module_size(module).unwrap_or(0),
+      "children": children,
+    })
+  }
+
+  fn build_treemap_package(
+    &mut self,
+    package_id: &NpmPackageId,
+  ) -> serde_json::Value {
+    let name = format!("npm:{}", package_id.as_serialized());
+    if self.seen.insert(package_id.as_serialized(), None).is_some() {
+      return json!({ "name": name, "value": 0 });
+    }
+    let mut dep_ids = self
+      .npm_info
+      .packages
+      .get(package_id)
+      .map(|package| package.dependencies.values().cloned().collect::<Vec<_>>())
+      .unwrap_or_default();
+    dep_ids.sort();
+    let children = dep_ids
+      .iter()
+      .map(|dep_id| self.build_treemap_package(dep_id))
+      .collect::<Vec<_>>();
+    json!({
+      "name": name,
+      "value": self.npm_info.package_sizes.get(package_id).copied().unwrap_or(0),
+      "children": children,
+    })
+  }
+}
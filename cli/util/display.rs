@@ -85,6 +85,18 @@ where
   Ok(())
 }
 
+/// Writes the value as single-line JSON, which is considerably smaller than
+/// the pretty printed output for large values.
+pub fn write_compact_json_to_stdout<T>(value: &T) -> Result<(), AnyError>
+where
+  T: ?Sized + serde::ser::Serialize,
+{
+  let mut writer = std::io::BufWriter::new(std::io::stdout());
+  serde_json::to_writer(&mut writer, value)?;
+  writeln!(&mut writer)?;
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -48,6 +48,10 @@ impl GlobPattern {
   pub fn matches_path(&self, path: &Path) -> bool {
     self.0.matches_path(path)
   }
+
+  pub fn matches_str(&self, text: &str) -> bool {
+    self.0.matches(text)
+  }
 }
 
 pub struct GlobSet(Vec<GlobPattern>);
@@ -65,6 +69,10 @@ impl GlobSet {
     }
     false
   }
+
+  pub fn matches_str(&self, text: &str) -> bool {
+    self.0.iter().any(|pattern| pattern.matches_str(text))
+  }
 }
 
 pub fn is_glob_pattern(path: &str) -> bool {
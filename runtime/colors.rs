@@ -2,6 +2,7 @@
 
 use atty;
 use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 use std::fmt;
 use std::io::Write;
 use termcolor::Ansi;
@@ -25,6 +26,8 @@ use termcolor::ColorChoice;
 static NO_COLOR: Lazy<bool> =
   Lazy::new(|| std::env::var_os("NO_COLOR").is_some());
 
+static USE_COLOR_OVERRIDE: OnceCell<bool> = OnceCell::new();
+
 static IS_TTY: Lazy<bool> = Lazy::new(|| atty::is(atty::Stream::Stdout));
 
 pub fn is_tty() -> bool {
@@ -32,7 +35,16 @@ pub fn is_tty() -> bool {
 }
 
 pub fn use_color() -> bool {
-  !(*NO_COLOR)
+  match USE_COLOR_OVERRIDE.get() {
+    Some(use_color) => *use_color,
+    None => !(*NO_COLOR),
+  }
+}
+
+/// Forces colors on or off regardless of the `NO_COLOR` environment
+/// variable. Only the first call has an effect.
+pub fn set_use_color(use_color: bool) {
+  let _ = USE_COLOR_OVERRIDE.set(use_color);
 }
 
 #[cfg(windows)]
@@ -141,6 +153,12 @@ pub fn gray<S: AsRef<str>>(s: S) -> impl fmt::Display {
   style(s, style_spec)
 }
 
+pub fn blue<S: AsRef<str>>(s: S) -> impl fmt::Display {
+  let mut style_spec = ColorSpec::new();
+  style_spec.set_fg(Some(Blue));
+  style(s, style_spec)
+}
+
 pub fn intense_blue<S: AsRef<str>>(s: S) -> impl fmt::Display {
   let mut style_spec = ColorSpec::new();
   style_spec.set_fg(Some(Blue)).set_intense(true);